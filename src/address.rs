@@ -0,0 +1,45 @@
+use std::sync::mpsc::*;
+
+/// A handle for sending data into a topology
+///
+/// Addresses are created by `Builder::mailbox` and can be cloned and sent
+/// to other threads.  Data sent to an address is received by the signal
+/// returned alongside it.  Once every copy of an address has been dropped
+/// the topology will exit, in the same way as when the sending side of a
+/// channel passed to `Builder::listen` is dropped.  Addresses created by
+/// `Builder::named_mailbox` are also held by the `TopologyHandle`, so they
+/// aren't all dropped until the handle is.
+///
+pub struct Address<A> {
+    tx: SyncSender<A>,
+}
+
+impl<A> Address<A> where
+    A: Send,
+{
+    pub fn new(tx: SyncSender<A>) -> Address<A> {
+        Address {
+            tx: tx,
+        }
+    }
+
+    /// Send `a` to the topology, blocking until it is accepted
+    ///
+    pub fn send(&self, a: A) -> Result<(), SendError<A>> {
+        self.tx.send(a)
+    }
+
+    /// Send `a` to the topology if it can be accepted without blocking
+    ///
+    pub fn try_send(&self, a: A) -> Result<(), TrySendError<A>> {
+        self.tx.try_send(a)
+    }
+}
+
+impl<A> Clone for Address<A> {
+    fn clone(&self) -> Address<A> {
+        Address {
+            tx: self.tx.clone(),
+        }
+    }
+}
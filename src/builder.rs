@@ -1,5 +1,13 @@
 use std::thread;
+use std::any::Any;
+use std::collections::HashMap;
+use std::io;
+use std::io::BufRead;
 use std::iter;
+use std::net::{TcpListener, UdpSocket, SocketAddr, ToSocketAddrs};
+#[cfg(unix)]
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::Path;
 use std::cell::*;
 use std::sync::*;
 use std::sync::mpsc::*;
@@ -9,11 +17,24 @@ use std::ops::Add;
 use rand;
 use time;
 
-use super::{Signal, SignalExt, Run, Config};
-use primitives::input::{RunInput, ReceiverInput, AckInput, RngInput};
+use super::{Signal, SignalExt, SignalType, Run, Config, Address, Value as V};
+#[cfg(unix)]
+use super::Framing;
+use primitives::input::{RunInput, ReceiverInput, AckInput, RngInput, DelayInput, DebounceInput, ThrottleInput, IterInput, LinesInput, EndPolicy};
+use primitives::file::{FileInput, FileStart};
+use primitives::tcp::TcpInput;
+#[cfg(unix)]
+use primitives::unix::{UnixInput, UnixSink};
+use primitives::udp::{UdpInput, DatagramPolicy, DatagramError, DatagramCounters, MAX_DATAGRAM_SIZE};
 use primitives::fork::{Fork, Branch};
 use primitives::channel::Channel;
 use primitives::async::Async;
+use primitives::delay::Delay;
+use primitives::partition::Partition;
+use primitives::try_lift::TryLift;
+use primitives::unzip::Unzip;
+use primitives::lift_all::LiftAllSignal;
+use primitives::fold_n::FoldAllSignal;
 use primitives::value::Value;
 
 /// `Builder` provides helpers for building topologies
@@ -22,6 +43,7 @@ pub struct Builder {
     config: Config,
     pub inputs: RefCell<Vec<Box<RunInput>>>,
     pub runners: RefCell<Vec<Box<Run>>>,
+    pub addresses: RefCell<HashMap<String, Box<Any + Send>>>,
 }
 
 impl Builder {
@@ -32,6 +54,7 @@ impl Builder {
             config: config,
             runners: RefCell::new(Vec::new()),
             inputs: RefCell::new(Vec::new()),
+            addresses: RefCell::new(HashMap::new()),
         }
     }
 
@@ -71,6 +94,528 @@ impl Builder {
         self.add(Channel::new(self.config.clone(), rx, initial))
     }
 
+    /// Push each item produced by `iter` into the topology
+    ///
+    /// Like `listen`, but data is read from an iterator rather than a channel,
+    /// which is convenient for tests and batch jobs.  Once the iterator is
+    /// exhausted `policy` determines whether the topology is shut down or
+    /// just stops receiving data from this input.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::default::*;
+    /// use std::sync::mpsc::*;
+    /// use cfrp::*;
+    ///
+    /// let (out_tx, out_rx) = channel();
+    ///
+    /// spawn_topology(Default::default(), move |t| {
+    ///     t.listen_iter(0, 1..4, EndPolicy::Exit)
+    ///         .fold(0, |sum, i| { sum + i })
+    ///         .lift(move |i| { out_tx.send(i).unwrap(); })
+    ///         .add_to(t);
+    /// });
+    ///
+    /// // Initial value, then one value per item
+    /// assert_eq!(out_rx.recv().unwrap(), 0);
+    /// assert_eq!(out_rx.recv().unwrap(), 1);
+    /// assert_eq!(out_rx.recv().unwrap(), 3);
+    /// assert_eq!(out_rx.recv().unwrap(), 6);
+    /// ```
+    ///
+    pub fn listen_iter<I, A>(&self, initial: A, iter: I, policy: EndPolicy) -> Branch<A> where
+        I: 'static + Send + Iterator<Item=A>,
+        A: 'static + Clone + Send,
+    {
+        let (tx, rx) = sync_channel(self.config.buffer_size.clone());
+
+        let runner = IterInput::new(iter, policy, tx);
+
+        self.inputs.borrow_mut().push(Box::new(runner));
+
+        self.add(Channel::new(self.config.clone(), rx, initial))
+    }
+
+    /// Push each line read from `reader` into the topology
+    ///
+    /// Each line is passed to `parse`, and the values it returns are sent to
+    /// the topology.  Lines which can't be parsed are ignored.  Once the end
+    /// of `reader` is reached (or reading fails) the topology is shut down,
+    /// in the same way as when the sending side of a channel passed to
+    /// `listen` is dropped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::default::*;
+    /// use std::io::Cursor;
+    /// use std::sync::mpsc::*;
+    /// use cfrp::*;
+    ///
+    /// let (out_tx, out_rx) = channel();
+    ///
+    /// spawn_topology(Default::default(), move |t| {
+    ///     t.listen_lines(Cursor::new("1\nnot a number\n2\n"), 0, |line| line.parse::<usize>().ok())
+    ///         .lift(move |i| { out_tx.send(i).unwrap(); })
+    ///         .add_to(t);
+    /// });
+    ///
+    /// // Initial value
+    /// assert_eq!(out_rx.recv().unwrap(), 0);
+    ///
+    /// assert_eq!(out_rx.recv().unwrap(), 1);
+    /// assert_eq!(out_rx.recv().unwrap(), 2);
+    /// ```
+    ///
+    pub fn listen_lines<R, F, A>(&self, reader: R, initial: A, parse: F) -> Branch<A> where
+        R: 'static + Send + BufRead,
+        F: 'static + Send + Fn(&str) -> Option<A>,
+        A: 'static + Clone + Send,
+    {
+        let (tx, rx) = sync_channel(self.config.buffer_size.clone());
+
+        let runner = LinesInput::new(reader, parse, tx);
+
+        self.inputs.borrow_mut().push(Box::new(runner));
+
+        self.add(Channel::new(self.config.clone(), rx, initial))
+    }
+
+    /// Push each line read from stdin into the topology
+    ///
+    /// Equivalent to `listen_lines` reading from stdin, so the topology is
+    /// shut down once stdin is closed.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::default::*;
+    /// use cfrp::*;
+    ///
+    /// spawn_topology(Default::default(), |t| {
+    ///     t.listen_stdin(0, |line| line.parse::<usize>().ok())
+    ///         .fold(0, |sum, i| { sum + i })
+    ///         .lift(|sum| { println!("{}", sum); })
+    ///         .add_to(t);
+    /// });
+    /// ```
+    ///
+    pub fn listen_stdin<F, A>(&self, initial: A, parse: F) -> Branch<A> where
+        F: 'static + Send + Fn(&str) -> Option<A>,
+        A: 'static + Clone + Send,
+    {
+        self.listen_lines(io::BufReader::new(io::stdin()), initial, parse)
+    }
+
+    /// Listen for newline-delimited frames on a TCP address
+    ///
+    /// Binds to `addr` immediately, returning an error if the address can't
+    /// be bound, otherwise the signal is returned along with the address it
+    /// was bound to (which is useful when binding to port 0).  Each line
+    /// received on any connection is passed to `decoder`, and the values it
+    /// returns are sent to the topology.  Lines which aren't valid UTF-8 or
+    /// can't be decoded are ignored.  Errors on a connection, including lines
+    /// longer than `MAX_FRAME_SIZE`, only close that connection.  The
+    /// listener is closed when the topology is stopped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::default::*;
+    /// use std::io::Write;
+    /// use std::net::TcpStream;
+    /// use std::sync::mpsc::*;
+    /// use cfrp::*;
+    ///
+    /// let (out_tx, out_rx) = channel();
+    /// let mut addr = None;
+    ///
+    /// spawn_topology(Default::default(), |t| {
+    ///     let (signal, bound) = t.listen_tcp("127.0.0.1:0", 0, |line| line.parse::<usize>().ok())
+    ///         .unwrap();
+    ///     addr = Some(bound);
+    ///
+    ///     signal
+    ///         .lift(move |i| { out_tx.send(i).unwrap(); })
+    ///         .add_to(t);
+    /// });
+    ///
+    /// // Initial value
+    /// assert_eq!(out_rx.recv().unwrap(), 0);
+    ///
+    /// let mut stream = TcpStream::connect(addr.unwrap()).unwrap();
+    /// stream.write_all(b"1\nnot a number\n2\n").unwrap();
+    ///
+    /// assert_eq!(out_rx.recv().unwrap(), 1);
+    /// assert_eq!(out_rx.recv().unwrap(), 2);
+    /// ```
+    ///
+    pub fn listen_tcp<S, F, A>(&self, addr: S, initial: A, decoder: F) -> io::Result<(Branch<A>, SocketAddr)> where
+        S: ToSocketAddrs,
+        F: 'static + Send + Fn(&str) -> Option<A>,
+        A: 'static + Clone + Send,
+    {
+        let listener = try!(TcpListener::bind(addr));
+        let addr = try!(listener.local_addr());
+
+        let (tx, rx) = sync_channel(self.config.buffer_size.clone());
+
+        let runner = TcpInput::new(listener, addr, self.config.buffer_size.clone(), decoder, tx);
+
+        self.inputs.borrow_mut().push(Box::new(runner));
+
+        Ok((self.add(Channel::new(self.config.clone(), rx, initial)), addr))
+    }
+
+    /// Listen for datagrams on a UDP address
+    ///
+    /// Binds to `bind_addr` immediately, returning an error if the address
+    /// can't be bound, otherwise the signal is returned along with the
+    /// address it was bound to.  Each datagram received is passed to `decode`
+    /// along with the address it was sent from, and the values it returns are
+    /// sent to the topology.  Datagrams which can't be decoded are dropped;
+    /// use `listen_udp_with` to count them.  The socket is closed when the
+    /// topology is stopped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::default::*;
+    /// use std::net::UdpSocket;
+    /// use std::str;
+    /// use std::sync::mpsc::*;
+    /// use cfrp::*;
+    ///
+    /// let (out_tx, out_rx) = channel();
+    /// let mut addr = None;
+    ///
+    /// spawn_topology(Default::default(), |t| {
+    ///     let (signal, bound) = t.listen_udp("127.0.0.1:0", 0, |bytes, _| {
+    ///         str::from_utf8(bytes).ok().and_then(|s| s.parse::<usize>().ok())
+    ///     }).unwrap();
+    ///     addr = Some(bound);
+    ///
+    ///     signal
+    ///         .lift(move |i| { out_tx.send(i).unwrap(); })
+    ///         .add_to(t);
+    /// });
+    ///
+    /// // Initial value
+    /// assert_eq!(out_rx.recv().unwrap(), 0);
+    ///
+    /// let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+    /// socket.send_to(b"not a number", addr.unwrap()).unwrap();
+    /// socket.send_to(b"1", addr.unwrap()).unwrap();
+    ///
+    /// assert_eq!(out_rx.recv().unwrap(), 1);
+    /// ```
+    ///
+    pub fn listen_udp<S, F, A>(&self, bind_addr: S, initial: A, decode: F) -> io::Result<(Branch<A>, SocketAddr)> where
+        S: ToSocketAddrs,
+        F: 'static + Send + Fn(&[u8], SocketAddr) -> Option<A>,
+        A: 'static + Clone + Send,
+    {
+        self.listen_udp_with(bind_addr, initial, MAX_DATAGRAM_SIZE, DatagramPolicy::Drop, decode)
+            .map(|(signal, _, addr, _)| (signal, addr))
+    }
+
+    /// Like `listen_udp`, but with a maximum datagram size and a policy for
+    /// rejected datagrams
+    ///
+    /// Datagrams larger than `max_size` bytes are rejected along with those
+    /// which can't be decoded.  With `DatagramPolicy::Error`, rejected
+    /// datagrams are sent to the returned error signal, otherwise they're
+    /// dropped.  Either way they're counted by the returned `DatagramCounters`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::default::*;
+    /// use std::net::UdpSocket;
+    /// use std::sync::mpsc::*;
+    /// use cfrp::*;
+    ///
+    /// let (out_tx, out_rx) = channel();
+    /// let (err_tx, err_rx) = channel();
+    /// let mut addr = None;
+    /// let mut counters = None;
+    ///
+    /// spawn_topology(Default::default(), |t| {
+    ///     let (bytes, errors, bound, counts) = t.listen_udp_with(
+    ///         "127.0.0.1:0",
+    ///         0,
+    ///         4,
+    ///         DatagramPolicy::Error,
+    ///         |bytes, _| Some(bytes.len()),
+    ///     ).unwrap();
+    ///     addr = Some(bound);
+    ///     counters = Some(counts);
+    ///
+    ///     bytes
+    ///         .lift(move |i| { out_tx.send(i).unwrap(); })
+    ///         .add_to(t);
+    ///
+    ///     errors
+    ///         .lift(move |e| { err_tx.send(e.is_some()).unwrap(); })
+    ///         .add_to(t);
+    /// });
+    ///
+    /// // Initial values
+    /// assert_eq!(out_rx.recv().unwrap(), 0);
+    /// assert_eq!(err_rx.recv().unwrap(), false);
+    ///
+    /// let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+    /// socket.send_to(b"too long", addr.unwrap()).unwrap();
+    /// assert_eq!(err_rx.recv().unwrap(), true);
+    ///
+    /// socket.send_to(b"ok", addr.unwrap()).unwrap();
+    /// assert_eq!(out_rx.recv().unwrap(), 2);
+    /// assert_eq!(counters.unwrap().counts().oversized, 1);
+    /// ```
+    ///
+    pub fn listen_udp_with<S, F, A>(
+        &self,
+        bind_addr: S,
+        initial: A,
+        max_size: usize,
+        policy: DatagramPolicy,
+        decode: F,
+    ) -> io::Result<(Branch<A>, Branch<Option<DatagramError>>, SocketAddr, DatagramCounters)> where
+        S: ToSocketAddrs,
+        F: 'static + Send + Fn(&[u8], SocketAddr) -> Option<A>,
+        A: 'static + Clone + Send,
+    {
+        let socket = try!(UdpSocket::bind(bind_addr));
+        let addr = try!(socket.local_addr());
+
+        let (tx, rx) = sync_channel(self.config.buffer_size.clone());
+        let (err_tx, err_rx) = sync_channel(self.config.buffer_size.clone());
+        let counters = DatagramCounters::new();
+
+        let runner = UdpInput::new(socket, addr, max_size, policy, decode, tx, err_tx, counters.clone());
+
+        self.inputs.borrow_mut().push(Box::new(runner));
+
+        Ok((
+            self.add(Channel::new(self.config.clone(), rx, initial)),
+            self.add(Channel::new(self.config.clone(), err_rx, None)),
+            addr,
+            counters,
+        ))
+    }
+
+    /// Listen for frames on a Unix domain socket
+    ///
+    /// Creates the socket file at `path` immediately, returning an error if
+    /// it can't be created.  Each frame received on any connection is split
+    /// out using `framing` and passed to `decode`, and the values it returns
+    /// are sent to the topology.  Frames which can't be decoded are ignored,
+    /// and errors on a connection only close that connection.  The socket
+    /// file is removed when the topology is stopped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::default::*;
+    /// use std::env;
+    /// use std::fs;
+    /// use std::io::Write;
+    /// use std::os::unix::net::UnixStream;
+    /// use std::sync::mpsc::*;
+    /// use cfrp::*;
+    ///
+    /// let path = env::temp_dir().join("cfrp-listen-unix.sock");
+    /// let _ = fs::remove_file(&path);
+    /// let (out_tx, out_rx) = channel();
+    ///
+    /// let socket_path = path.clone();
+    /// let mut handle = spawn_topology(Default::default(), move |t| {
+    ///     t.listen_unix(socket_path, 0, Newline::new(), |frame| Some(frame.len()))
+    ///         .unwrap()
+    ///         .lift(move |i| { out_tx.send(i).unwrap(); })
+    ///         .add_to(t);
+    /// });
+    ///
+    /// // Initial value
+    /// assert_eq!(out_rx.recv().unwrap(), 0);
+    ///
+    /// let mut stream = UnixStream::connect(&path).unwrap();
+    /// stream.write_all(b"abc\n").unwrap();
+    /// assert_eq!(out_rx.recv().unwrap(), 3);
+    ///
+    /// handle.stop();
+    /// assert!(!path.exists());
+    /// ```
+    ///
+    #[cfg(unix)]
+    pub fn listen_unix<P, Fr, F, A>(&self, path: P, initial: A, framing: Fr, decode: F) -> io::Result<Branch<A>> where
+        P: AsRef<Path>,
+        Fr: Framing,
+        F: 'static + Send + Fn(&[u8]) -> Option<A>,
+        A: 'static + Clone + Send,
+    {
+        let listener = try!(UnixListener::bind(path.as_ref()));
+
+        let (tx, rx) = sync_channel(self.config.buffer_size.clone());
+
+        let runner = UnixInput::new(listener, path.as_ref().to_path_buf(), self.config.buffer_size.clone(), framing, decode, tx);
+
+        self.inputs.borrow_mut().push(Box::new(runner));
+
+        Ok(self.add(Channel::new(self.config.clone(), rx, initial)))
+    }
+
+    /// Follow the lines appended to a file, like `tail -F`
+    ///
+    /// The file at `path` is polled every `interval`, and each new line is
+    /// passed to `parse`.  The values it returns are sent to the topology,
+    /// lines which can't be parsed are ignored.  `start` determines whether
+    /// the file's existing lines are read, and is applied when this method is
+    /// called.  If the file is truncated it's read again from the beginning,
+    /// and if it's replaced by a new file (for instance by log rotation) the
+    /// new file is followed from its beginning.  The file doesn't need to
+    /// exist when the topology starts.  Panics if `interval` is negative.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate cfrp;
+    /// extern crate time;
+    ///
+    /// use std::default::*;
+    /// use std::env;
+    /// use std::fs::{File, OpenOptions};
+    /// use std::io::Write;
+    /// use std::sync::mpsc::*;
+    /// use cfrp::*;
+    ///
+    /// fn main() {
+    ///     let path = env::temp_dir().join("cfrp-listen-file.log");
+    ///     File::create(&path).unwrap().write_all(b"1\n").unwrap();
+    ///     let (out_tx, out_rx) = channel();
+    ///
+    ///     let log_path = path.clone();
+    ///     spawn_topology(Default::default(), move |t| {
+    ///         t.listen_file(log_path, 0, FileStart::Beginning, time::Duration::milliseconds(10), |line| {
+    ///             line.parse::<usize>().ok()
+    ///         })
+    ///             .lift(move |i| { out_tx.send(i).unwrap(); })
+    ///             .add_to(t);
+    ///     });
+    ///
+    ///     // Initial value
+    ///     assert_eq!(out_rx.recv().unwrap(), 0);
+    ///
+    ///     // Existing lines
+    ///     assert_eq!(out_rx.recv().unwrap(), 1);
+    ///
+    ///     let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+    ///     file.write_all(b"2\n").unwrap();
+    ///     assert_eq!(out_rx.recv().unwrap(), 2);
+    /// }
+    /// ```
+    ///
+    pub fn listen_file<P, F, A>(&self, path: P, initial: A, start: FileStart, interval: time::Duration, parse: F) -> Branch<A> where
+        P: AsRef<Path>,
+        F: 'static + Send + Fn(&str) -> Option<A>,
+        A: 'static + Clone + Send,
+    {
+        assert!(interval >= time::Duration::zero(), "file poll interval must not be negative");
+
+        let (tx, rx) = sync_channel(self.config.buffer_size.clone());
+
+        let runner = FileInput::new(path.as_ref().to_path_buf(), start, interval.to_std().unwrap(), parse, tx);
+
+        self.inputs.borrow_mut().push(Box::new(runner));
+
+        self.add(Channel::new(self.config.clone(), rx, initial))
+    }
+
+    /// Create an input which can be sent data through an `Address`
+    ///
+    /// Equivalent to calling `listen` with a new channel, but the topology
+    /// creates the channel and hands out its sending side as an `Address`,
+    /// which can be cloned and sent to other threads.  Use
+    /// `spawn_topology_with` to return the address from the builder closure.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::default::*;
+    /// use std::sync::mpsc::*;
+    /// use cfrp::*;
+    ///
+    /// let (out_tx, out_rx) = channel();
+    ///
+    /// let (_, address) = spawn_topology_with(Default::default(), move |t| {
+    ///     let (address, signal) = t.mailbox(0);
+    ///
+    ///     signal
+    ///         .lift(move |i| { out_tx.send(i).unwrap(); })
+    ///         .add_to(t);
+    ///
+    ///     address
+    /// });
+    ///
+    /// // Initial value
+    /// assert_eq!(out_rx.recv().unwrap(), 0);
+    ///
+    /// address.send(1).unwrap();
+    /// assert_eq!(out_rx.recv().unwrap(), 1);
+    /// ```
+    ///
+    pub fn mailbox<A>(&self, initial: A) -> (Address<A>, Branch<A>) where
+        A: 'static + Clone + Send,
+    {
+        let (tx, rx) = sync_channel(self.config.buffer_size.clone());
+
+        (Address::new(tx), self.listen(initial, rx))
+    }
+
+    /// Like `mailbox`, but the address is also available from the topology's
+    /// handle as `TopologyHandle::address(name)`
+    ///
+    /// The handle keeps its own copy of the address, so the input isn't
+    /// closed by dropping addresses while the handle is alive.  Panics if
+    /// `name` has already been used.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::default::*;
+    /// use std::sync::mpsc::*;
+    /// use cfrp::*;
+    ///
+    /// let (out_tx, out_rx) = channel();
+    ///
+    /// let handle = spawn_topology(Default::default(), move |t| {
+    ///     t.named_mailbox("input", 0)
+    ///         .lift(move |i| { out_tx.send(i).unwrap(); })
+    ///         .add_to(t);
+    /// });
+    ///
+    /// // Initial value
+    /// assert_eq!(out_rx.recv().unwrap(), 0);
+    ///
+    /// handle.address("input").unwrap().send(1).unwrap();
+    /// assert_eq!(out_rx.recv().unwrap(), 1);
+    /// ```
+    ///
+    pub fn named_mailbox<S, A>(&self, name: S, initial: A) -> Branch<A> where
+        S: Into<String>,
+        A: 'static + Clone + Send,
+    {
+        let name = name.into();
+        assert!(!self.addresses.borrow().contains_key(&name), "mailbox name `{}` is already in use", name);
+
+        let (address, signal) = self.mailbox(initial);
+
+        self.addresses.borrow_mut().insert(name, Box::new(address));
+
+        signal
+    }
+
     /// Creats a channel with constant value `v`
     /// 
     /// Nodes downstream of values will be executed once on initialization and
@@ -186,54 +731,224 @@ impl Builder {
     /// themselves to be recomputed. By contrast, signals created by `ack_*` will
     /// emit a value when any input signal's value changes.  
     ///
-    pub fn ack_counter<A, B>(&self, initial: A, by: A) -> Branch<A> where
-    A: 'static + Clone + Send + Add<Output=A>,
+    pub fn ack_counter<A, B>(&self, initial: A, by: A) -> Branch<A> where
+    A: 'static + Clone + Send + Add<Output=A>,
+    {
+        self.add(
+            self.ack_value(by)
+            .fold(initial, |c, incr| { c + incr })
+        )
+    }
+
+    /// Return a signal with the 'current' time each time the topology receives
+    /// data
+    ///
+    /// Signals created with `listen` only cause nodes directly downstream of 
+    /// themselves to be recomputed. By contrast, signals created by `ack_*` will
+    /// emit a value when any input signal's value changes.  
+    ///
+    pub fn ack_timestamp(&self) -> Branch<time::Tm>
+    {
+        self.add(
+            self.ack_value(())
+            .lift(|_| -> time::Tm { time::now() })
+        )
+    }
+
+    /// Return a signal which generates a random value each time the topology
+    /// receives data
+    ///
+    /// If randomness is needed, `ack_random` is probably a better way of generating it
+    /// than creating a Rng inside a handler because it exposes the generated 
+    /// value as a 'fact' about the system's history rather than embedding it
+    /// in a non-deterministic function.  
+    ///
+    /// Signals created with `listen` only cause nodes directly downstream of 
+    /// themselves to be recomputed. By contrast, signals created by `ack_*` will
+    /// emit a value when any input signal's value changes.  
+    ///
+    pub fn ack_random<R, A>(&self, mut rng: R) -> Branch<A> where
+    R: 'static + rand::Rng + Clone + Send,
+    A: 'static + Send + Clone + rand::Rand,
+    {
+        let (tx, rx) = sync_channel(self.config.buffer_size.clone());
+
+        let initial = rng.gen();
+        let runner = RngInput::new(rng, tx);
+
+        self.inputs.borrow_mut().push(Box::new(runner));
+
+        self.add(Channel::new(self.config.clone(), rx, initial))
+    }
+
+    /// Merge any number of same-typed signals into a single signal
+    ///
+    /// Each tick the output takes the value of the first signal in `signals`
+    /// which changed, so signals earlier in the vector have priority over
+    /// later ones.  The initial value is the initial value of the first
+    /// signal.  Use `merge_all_changed` to receive every changed value.
+    ///
+    /// Panics if `signals` is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::default::*;
+    /// use std::sync::mpsc::*;
+    /// use cfrp::*;
+    ///
+    /// let (tx, rx) = sync_channel(0);
+    /// let (out_tx, out_rx) = channel();
+    ///
+    /// spawn_topology(Default::default(), move |t| {
+    ///     let signals: Vec<Box<Signal<usize>>> = vec![
+    ///         Box::new(t.value(0)),
+    ///         Box::new(t.listen(1, rx)),
+    ///     ];
+    ///
+    ///     t.merge_all(signals)
+    ///         .lift(move |i| { out_tx.send(i).unwrap(); })
+    ///         .add_to(t);
+    /// });
+    ///
+    /// // Initial value
+    /// assert_eq!(out_rx.recv().unwrap(), 0);
+    ///
+    /// tx.send(2).unwrap();
+    /// assert_eq!(out_rx.recv().unwrap(), 2);
+    /// ```
+    ///
+    pub fn merge_all<A>(&self, signals: Vec<Box<Signal<A>>>) -> Branch<A> where
+        A: 'static + Clone + Send,
+    {
+        assert!(!signals.is_empty(), "merge_all requires at least one signal");
+
+        self.add(
+            self.lift_all(signals, |vs: Vec<V<A>>| -> A {
+                let mut first = None;
+                for v in vs.into_iter() {
+                    match v {
+                        V::Changed(a) => return a,
+                        V::Unchanged(a) => if first.is_none() { first = Some(a) },
+                    }
+                }
+                first.unwrap()
+            })
+        )
+    }
+
+    /// Merge any number of same-typed signals into a signal containing each
+    /// changed value and the index of the signal it came from
+    ///
+    /// Values are ordered by index.  The initial value contains the initial
+    /// value of every signal.
+    ///
+    pub fn merge_all_changed<A>(&self, signals: Vec<Box<Signal<A>>>) -> Branch<Vec<(usize, A)>> where
+        A: 'static + Clone + Send,
+    {
+        self.add(
+            self.lift_all(signals, |vs: Vec<V<A>>| -> Vec<(usize, A)> {
+                vs.into_iter()
+                    .enumerate()
+                    .filter_map(|(i, v)| {
+                        match v {
+                            V::Changed(a) => Some((i, a)),
+                            V::Unchanged(_) => None,
+                        }
+                    })
+                    .collect()
+            })
+        )
+    }
+
+    /// Combine any number of same-typed signals into a signal containing the
+    /// most recent value of each
+    ///
+    /// Equivalent to Elm's `combine`.  Use `combine_values` to determine which
+    /// of the signals changed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::default::*;
+    /// use std::sync::mpsc::*;
+    /// use cfrp::*;
+    ///
+    /// let (a_tx, a_rx) = sync_channel(0);
+    /// let (b_tx, b_rx) = sync_channel(0);
+    /// let (out_tx, out_rx) = channel();
+    ///
+    /// spawn_topology(Default::default(), move |t| {
+    ///     t.combine(vec![t.listen(0, a_rx), t.listen(1, b_rx)])
+    ///         .lift(move |v| { out_tx.send(v).unwrap(); })
+    ///         .add_to(t);
+    /// });
+    ///
+    /// // Initial value
+    /// assert_eq!(out_rx.recv().unwrap(), vec![0, 1]);
+    ///
+    /// b_tx.send(2).unwrap();
+    /// assert_eq!(out_rx.recv().unwrap(), vec![0, 2]);
+    ///
+    /// a_tx.send(3).unwrap();
+    /// assert_eq!(out_rx.recv().unwrap(), vec![3, 2]);
+    /// ```
+    ///
+    pub fn combine<SA, A>(&self, signals: Vec<SA>) -> Branch<Vec<A>> where
+        SA: 'static + Signal<A>,
+        A: 'static + Clone + Send,
     {
+        let signals = signals.into_iter().map(|s| Box::new(s) as Box<Signal<A>>).collect();
+
         self.add(
-            self.ack_value(by)
-            .fold(initial, |c, incr| { c + incr })
+            self.lift_all(signals, |vs: Vec<V<A>>| -> Vec<A> {
+                vs.into_iter().map(|v| v.into_inner()).collect()
+            })
         )
     }
 
-    /// Return a signal with the 'current' time each time the topology receives
-    /// data
-    ///
-    /// Signals created with `listen` only cause nodes directly downstream of 
-    /// themselves to be recomputed. By contrast, signals created by `ack_*` will
-    /// emit a value when any input signal's value changes.  
+    /// Like `combine`, but each value is wrapped in a `Value` indicating
+    /// whether it changed
     ///
-    pub fn ack_timestamp(&self) -> Branch<time::Tm>
+    pub fn combine_values<SA, A>(&self, signals: Vec<SA>) -> Branch<Vec<V<A>>> where
+        SA: 'static + Signal<A>,
+        A: 'static + Clone + Send,
     {
-        self.add(
-            self.ack_value(())
-            .lift(|_| -> time::Tm { time::now() })
-        )
+        let signals = signals.into_iter().map(|s| Box::new(s) as Box<Signal<A>>).collect();
+
+        self.add(self.lift_all(signals, |vs: Vec<V<A>>| -> Vec<V<A>> { vs }))
     }
 
-    /// Return a signal which generates a random value each time the topology
-    /// receives data
-    ///
-    /// If randomness is needed, `ack_random` is probably a better way of generating it
-    /// than creating a Rng inside a handler because it exposes the generated 
-    /// value as a 'fact' about the system's history rather than embedding it
-    /// in a non-deterministic function.  
+    /// Merge data from any number of same-typed signals into an accumulator
+    /// and return a signal with the accumulator's value
     ///
-    /// Signals created with `listen` only cause nodes directly downstream of 
-    /// themselves to be recomputed. By contrast, signals created by `ack_*` will
-    /// emit a value when any input signal's value changes.  
+    /// `f` receives a `Value` for each signal, in order, and is only called
+    /// when at least one of them changed.
     ///
-    pub fn ack_random<R, A>(&self, mut rng: R) -> Branch<A> where
-    R: 'static + rand::Rng + Clone + Send,
-    A: 'static + Send + Clone + rand::Rand,
+    pub fn fold_all<SA, F, A, S>(&self, signals: Vec<SA>, initial: S, f: F) -> Branch<S> where
+        SA: 'static + Signal<A>,
+        F: 'static + Send + Fn(S, Vec<V<A>>) -> S,
+        A: 'static + Clone + Send,
+        S: 'static + Clone + Send,
     {
-        let (tx, rx) = sync_channel(self.config.buffer_size.clone());
+        let signals = signals.into_iter().map(|mut s| {
+            s.init();
+            Box::new(s) as Box<Signal<A>>
+        }).collect();
 
-        let initial = rng.gen();
-        let runner = RngInput::new(rng, tx);
+        self.add(FoldAllSignal::new(self.config.clone(), signals, initial, f))
+    }
 
-        self.inputs.borrow_mut().push(Box::new(runner));
+    fn lift_all<F, A, B>(&self, mut signals: Vec<Box<Signal<A>>>, f: F) -> LiftAllSignal<F, A, B> where
+        F: 'static + Send + Fn(Vec<V<A>>) -> B,
+        A: 'static + Clone + Send,
+        B: 'static + Clone + Send,
+    {
+        for signal in signals.iter_mut() {
+            signal.init();
+        }
 
-        self.add(Channel::new(self.config.clone(), rx, initial))
+        LiftAllSignal::new(self.config.clone(), signals, f)
     }
 
     /// Add a signal to the topology
@@ -279,6 +994,224 @@ impl Builder {
         Branch::new(self.config.clone(), fork_txs, None, v)
     }
 
+    /// Add a signal to the topology, splitting it into two signals using the
+    /// predicate `f`
+    ///
+    /// Values which satisfy `f` are sent to the first signal, all other values
+    /// are sent to the second.  Each value is only evaluated once, and the
+    /// signal which doesn't receive a value sees it as unchanged.  Both
+    /// signals start with the initial value of `root`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::default::*;
+    /// use std::sync::mpsc::*;
+    /// use cfrp::*;
+    ///
+    /// let (in_tx, in_rx) = sync_channel(0);
+    /// let (even_tx, even_rx) = channel();
+    /// let (odd_tx, odd_rx) = channel();
+    ///
+    /// spawn_topology(Default::default(), move |t| {
+    ///     let (even, odd) = t.partition(t.listen(0, in_rx), |i| { i % 2 == 0 });
+    ///
+    ///     even.lift(move |i| { even_tx.send(i).unwrap(); }).add_to(t);
+    ///     odd.lift(move |i| { odd_tx.send(i).unwrap(); }).add_to(t);
+    /// });
+    ///
+    /// // Initial values
+    /// assert_eq!(even_rx.recv().unwrap(), 0);
+    /// assert_eq!(odd_rx.recv().unwrap(), 0);
+    ///
+    /// in_tx.send(1).unwrap();
+    /// in_tx.send(2).unwrap();
+    /// assert_eq!(odd_rx.recv().unwrap(), 1);
+    /// assert_eq!(even_rx.recv().unwrap(), 2);
+    /// ```
+    ///
+    pub fn partition<SA, F, A>(&self, mut root: SA, f: F) -> (Branch<A>, Branch<A>) where
+        SA: 'static + Signal<A>,
+        F: 'static + Send + Fn(&A) -> bool,
+        A: 'static + Clone + Send,
+    {
+        root.init();
+
+        let v = root.initial();
+
+        let matched_txs = Arc::new(Mutex::new(Vec::new()));
+        let unmatched_txs = Arc::new(Mutex::new(Vec::new()));
+
+        let partition = Partition::new(Box::new(root), f, matched_txs.clone(), unmatched_txs.clone());
+
+        self.runners.borrow_mut().push(Box::new(partition));
+
+        (
+            Branch::new(self.config.clone(), matched_txs, None, v.clone()),
+            Branch::new(self.config.clone(), unmatched_txs, None, v),
+        )
+    }
+
+    /// Add a signal to the topology, transforming it with a function which
+    /// may fail
+    ///
+    /// Returns the transformed signal and a signal of errors.  When `f`
+    /// fails, the transformed signal keeps its last successful value and the
+    /// error signal changes to the error; otherwise the error signal sees the
+    /// value as unchanged.  If `f` fails for the initial value of `root`,
+    /// `default` is used instead and the error is the error signal's initial
+    /// value, otherwise its initial value is `default_err`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::default::*;
+    /// use std::sync::mpsc::*;
+    /// use cfrp::*;
+    ///
+    /// let (in_tx, in_rx) = sync_channel(0);
+    /// let (out_tx, out_rx) = channel();
+    /// let (err_tx, err_rx) = channel();
+    ///
+    /// spawn_topology(Default::default(), move |t| {
+    ///     let (parsed, errors) = t.try_lift(
+    ///         t.listen("1", in_rx),
+    ///         |s| { s.parse::<usize>().map_err(|e| e.to_string()) },
+    ///         0,
+    ///         String::new(),
+    ///     );
+    ///
+    ///     parsed.lift(move |i| { out_tx.send(i).unwrap(); }).add_to(t);
+    ///     errors.lift(move |e| { err_tx.send(e).unwrap(); }).add_to(t);
+    /// });
+    ///
+    /// // Initial values
+    /// assert_eq!(out_rx.recv().unwrap(), 1);
+    /// assert_eq!(err_rx.recv().unwrap(), "");
+    ///
+    /// in_tx.send("foo").unwrap();
+    /// assert_eq!(err_rx.recv().unwrap(), "invalid digit found in string");
+    ///
+    /// in_tx.send("2").unwrap();
+    /// assert_eq!(out_rx.recv().unwrap(), 2);
+    /// ```
+    ///
+    pub fn try_lift<SA, F, A, B, E>(&self, mut root: SA, f: F, default: B, default_err: E) -> (Branch<B>, Branch<E>) where
+        SA: 'static + Signal<A>,
+        F: 'static + Send + Fn(A) -> Result<B, E>,
+        A: 'static + Clone + Send,
+        B: 'static + Clone + Send,
+        E: 'static + Clone + Send,
+    {
+        root.init();
+
+        let (v, err) = match root.initial() {
+            SignalType::Constant(a) => {
+                match f(a) {
+                    Ok(b) => (SignalType::Constant(b), SignalType::Constant(default_err)),
+                    Err(e) => (SignalType::Constant(default), SignalType::Constant(e)),
+                }
+            },
+            SignalType::Dynamic(a) => {
+                match f(a) {
+                    Ok(b) => (SignalType::Dynamic(b), SignalType::Dynamic(default_err)),
+                    Err(e) => (SignalType::Dynamic(default), SignalType::Dynamic(e)),
+                }
+            },
+        };
+
+        let ok_txs = Arc::new(Mutex::new(Vec::new()));
+        let err_txs = Arc::new(Mutex::new(Vec::new()));
+
+        let try_lift = TryLift::new(Box::new(root), f, ok_txs.clone(), err_txs.clone());
+
+        self.runners.borrow_mut().push(Box::new(try_lift));
+
+        (
+            Branch::new(self.config.clone(), ok_txs, None, v),
+            Branch::new(self.config.clone(), err_txs, None, err),
+        )
+    }
+
+    /// Add a signal of pairs to the topology, splitting it into a signal for
+    /// each component
+    ///
+    /// The inverse of `SignalExt::zip`.  Each component is only reported as
+    /// changed if it differs from the component's previous value.  For types
+    /// which don't implement `PartialEq` use `unzip_all`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::default::*;
+    /// use std::sync::mpsc::*;
+    /// use cfrp::*;
+    ///
+    /// let (in_tx, in_rx) = sync_channel(0);
+    /// let (l_tx, l_rx) = channel();
+    /// let (r_tx, r_rx) = channel();
+    ///
+    /// spawn_topology(Default::default(), move |t| {
+    ///     let (l, r) = t.unzip(t.listen((0, 0), in_rx));
+    ///
+    ///     l.lift(move |i| { l_tx.send(i).unwrap(); }).add_to(t);
+    ///     r.lift(move |i| { r_tx.send(i).unwrap(); }).add_to(t);
+    /// });
+    ///
+    /// // Initial values
+    /// assert_eq!(l_rx.recv().unwrap(), 0);
+    /// assert_eq!(r_rx.recv().unwrap(), 0);
+    ///
+    /// in_tx.send((0, 1)).unwrap();
+    /// in_tx.send((2, 1)).unwrap();
+    /// assert_eq!(r_rx.recv().unwrap(), 1);
+    /// assert_eq!(l_rx.recv().unwrap(), 2);
+    /// ```
+    ///
+    pub fn unzip<SA, A, B>(&self, root: SA) -> (Branch<A>, Branch<B>) where
+        SA: 'static + Signal<(A, B)>,
+        A: 'static + Clone + Send + PartialEq,
+        B: 'static + Clone + Send + PartialEq,
+    {
+        self.unzip_with(root, eq::<A>, eq::<B>)
+    }
+
+    /// Like `unzip`, but both components are reported as changed whenever
+    /// `root` changes
+    ///
+    pub fn unzip_all<SA, A, B>(&self, root: SA) -> (Branch<A>, Branch<B>) where
+        SA: 'static + Signal<(A, B)>,
+        A: 'static + Clone + Send,
+        B: 'static + Clone + Send,
+    {
+        self.unzip_with(root, never_eq::<A>, never_eq::<B>)
+    }
+
+    fn unzip_with<SA, A, B>(&self, mut root: SA, eq_a: fn(&A, &A) -> bool, eq_b: fn(&B, &B) -> bool) -> (Branch<A>, Branch<B>) where
+        SA: 'static + Signal<(A, B)>,
+        A: 'static + Clone + Send,
+        B: 'static + Clone + Send,
+    {
+        root.init();
+
+        let (a, b) = match root.initial() {
+            SignalType::Constant((a, b)) => (SignalType::Constant(a), SignalType::Constant(b)),
+            SignalType::Dynamic((a, b)) => (SignalType::Dynamic(a), SignalType::Dynamic(b)),
+        };
+
+        let left_txs = Arc::new(Mutex::new(Vec::new()));
+        let right_txs = Arc::new(Mutex::new(Vec::new()));
+
+        let unzip = Unzip::new(Box::new(root), eq_a, eq_b, left_txs.clone(), right_txs.clone());
+
+        self.runners.borrow_mut().push(Box::new(unzip));
+
+        (
+            Branch::new(self.config.clone(), left_txs, None, a),
+            Branch::new(self.config.clone(), right_txs, None, b),
+        )
+    }
+
     /// Combination of adding a signal and a channel
     ///
     /// Async allows signals to be processed downstream out of order.  Internally,
@@ -335,4 +1268,278 @@ impl Builder {
         self.listen(v.unwrap(), rx)
     }
 
+    /// Emit the most recent value of `root` once it has stopped changing for
+    /// `duration`
+    ///
+    /// Like `async`, the output of `root` is sent to a new input channel, so
+    /// the debounced value re-enters the topology as a new event.  Bursts of
+    /// changes shorter than `duration` result in a single change containing
+    /// the last value of the burst.  A value which is still pending when the
+    /// topology is stopped is dropped.  Panics if `duration` is negative.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate time;
+    /// extern crate cfrp;
+    ///
+    /// use std::sync::mpsc::*;
+    /// use cfrp::*;
+    ///
+    /// fn main() {
+    ///     let (in_tx, in_rx) = channel();
+    ///     let (out_tx, out_rx) = channel();
+    ///
+    ///     spawn_topology(Default::default(), move |t| {
+    ///         t.listen(0, in_rx)
+    ///             .debounce(t, time::Duration::milliseconds(50))
+    ///             .lift(move |i| { out_tx.send(i).unwrap(); })
+    ///             .add_to(t);
+    ///     });
+    ///
+    ///     // Initial value
+    ///     assert_eq!(out_rx.recv().unwrap(), 0);
+    ///
+    ///     in_tx.send(1).unwrap();
+    ///     in_tx.send(2).unwrap();
+    ///     in_tx.send(3).unwrap();
+    ///     assert_eq!(out_rx.recv().unwrap(), 3);
+    /// }
+    /// ```
+    ///
+    pub fn debounce<SA, A>(&self, mut root: SA, duration: time::Duration) -> Branch<A> where
+        SA: 'static + Signal<A>,
+        A: 'static + Clone + Send,
+    {
+        assert!(duration >= time::Duration::zero(), "debounce duration must not be negative");
+
+        root.init();
+
+        let v = root.initial();
+
+        // Unbounded like `delay`, so upstream nodes aren't blocked while the
+        // input is waiting to send
+        let (tx, rx) = channel();
+        let pusher = Delay::new(Box::new(root), tx);
+        self.runners.borrow_mut().push(Box::new(pusher));
+
+        let (debounced_tx, debounced_rx) = sync_channel(self.config.buffer_size.clone());
+        let runner = DebounceInput::new(rx, duration.to_std().unwrap(), debounced_tx);
+        self.inputs.borrow_mut().push(Box::new(runner));
+
+        self.add(Channel::new(self.config.clone(), debounced_rx, v.unwrap()))
+    }
+
+    /// Limit changes to `root` to at most one per `duration`
+    ///
+    /// Like `async`, the output of `root` is sent to a new input channel, so
+    /// the throttled value re-enters the topology as a new event.  Changes
+    /// which are suppressed never enter the topology; the rest of the topology
+    /// sees them as unchanged.  `mode` determines whether the first or the
+    /// last value received during each interval is emitted, see
+    /// `ThrottleMode`.  A trailing value which is still pending when the
+    /// topology is stopped is dropped.  Panics if `duration` is negative.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate time;
+    /// extern crate cfrp;
+    ///
+    /// use std::sync::mpsc::*;
+    /// use cfrp::*;
+    ///
+    /// fn main() {
+    ///     let (in_tx, in_rx) = channel();
+    ///     let (out_tx, out_rx) = channel();
+    ///
+    ///     spawn_topology(Default::default(), move |t| {
+    ///         t.listen(0, in_rx)
+    ///             .throttle(t, time::Duration::milliseconds(50), ThrottleMode::Both)
+    ///             .lift(move |i| { out_tx.send(i).unwrap(); })
+    ///             .add_to(t);
+    ///     });
+    ///
+    ///     // Initial value
+    ///     assert_eq!(out_rx.recv().unwrap(), 0);
+    ///
+    ///     in_tx.send(1).unwrap();
+    ///     in_tx.send(2).unwrap();
+    ///     in_tx.send(3).unwrap();
+    ///
+    ///     // Leading edge...
+    ///     assert_eq!(out_rx.recv().unwrap(), 1);
+    ///     // ...and trailing edge
+    ///     assert_eq!(out_rx.recv().unwrap(), 3);
+    /// }
+    /// ```
+    ///
+    pub fn throttle<SA, A>(&self, mut root: SA, duration: time::Duration, mode: ThrottleMode) -> Branch<A> where
+        SA: 'static + Signal<A>,
+        A: 'static + Clone + Send,
+    {
+        assert!(duration >= time::Duration::zero(), "throttle duration must not be negative");
+
+        root.init();
+
+        let v = root.initial();
+
+        // Unbounded like `delay`, so upstream nodes aren't blocked while the
+        // input is waiting to send
+        let (tx, rx) = channel();
+        let pusher = Delay::new(Box::new(root), tx);
+        self.runners.borrow_mut().push(Box::new(pusher));
+
+        let (throttled_tx, throttled_rx) = sync_channel(self.config.buffer_size.clone());
+        let runner = ThrottleInput::new(rx, duration.to_std().unwrap(), mode, throttled_tx);
+        self.inputs.borrow_mut().push(Box::new(runner));
+
+        self.add(Channel::new(self.config.clone(), throttled_rx, v.unwrap()))
+    }
+
+    /// Re-emit each change to `root` after `duration` has passed
+    ///
+    /// Changes are buffered with the time they were received and sent back
+    /// into the topology, in order, as new events once `duration` has passed.
+    /// Changes which are still buffered when the topology is stopped are
+    /// dropped.  Panics if `duration` is negative.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate time;
+    /// extern crate cfrp;
+    ///
+    /// use std::sync::mpsc::*;
+    /// use cfrp::*;
+    ///
+    /// fn main() {
+    ///     let (in_tx, in_rx) = channel();
+    ///     let (out_tx, out_rx) = channel();
+    ///
+    ///     spawn_topology(Default::default(), move |t| {
+    ///         t.listen(0, in_rx)
+    ///             .delay(t, time::Duration::milliseconds(50))
+    ///             .lift(move |i| { out_tx.send((i, time::now())).unwrap(); })
+    ///             .add_to(t);
+    ///     });
+    ///
+    ///     // Initial value
+    ///     assert_eq!(out_rx.recv().unwrap().0, 0);
+    ///
+    ///     let sent = time::now();
+    ///     in_tx.send(1).unwrap();
+    ///     in_tx.send(2).unwrap();
+    ///
+    ///     let (i, received) = out_rx.recv().unwrap();
+    ///     assert_eq!(i, 1);
+    ///     assert!(received - sent >= time::Duration::milliseconds(50));
+    ///     assert_eq!(out_rx.recv().unwrap().0, 2);
+    /// }
+    /// ```
+    ///
+    pub fn delay<SA, A>(&self, mut root: SA, duration: time::Duration) -> Branch<A> where
+        SA: 'static + Signal<A>,
+        A: 'static + Clone + Send,
+    {
+        assert!(duration >= time::Duration::zero(), "delay duration must not be negative");
+
+        root.init();
+
+        let v = root.initial();
+
+        // Unbounded, so upstream nodes aren't blocked while values are held
+        let (tx, rx) = channel();
+        let pusher = Delay::new(Box::new(root), tx);
+        self.runners.borrow_mut().push(Box::new(pusher));
+
+        let (delayed_tx, delayed_rx) = sync_channel(self.config.buffer_size.clone());
+        let runner = DelayInput::new(rx, duration.to_std().unwrap(), delayed_tx);
+        self.inputs.borrow_mut().push(Box::new(runner));
+
+        self.add(Channel::new(self.config.clone(), delayed_rx, v.unwrap()))
+    }
+
+    /// Write the initial value and each change of `root` to a Unix domain
+    /// socket
+    ///
+    /// Connects to the socket at `path` immediately, returning an error if
+    /// the connection fails.  Each value is converted to a frame with
+    /// `encode` and written using `framing`.  Write errors are logged but
+    /// don't stop the topology.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::default::*;
+    /// use std::env;
+    /// use std::fs;
+    /// use std::io::BufReader;
+    /// use std::os::unix::net::UnixListener;
+    /// use std::sync::mpsc::*;
+    /// use cfrp::*;
+    ///
+    /// let path = env::temp_dir().join("cfrp-write-unix.sock");
+    /// let _ = fs::remove_file(&path);
+    /// let listener = UnixListener::bind(&path).unwrap();
+    /// let (in_tx, in_rx) = sync_channel(0);
+    ///
+    /// let socket_path = path.clone();
+    /// spawn_topology(Default::default(), move |t| {
+    ///     t.listen(0, in_rx)
+    ///         .write_unix(t, socket_path, LengthPrefixed::new(), |i: &usize| i.to_string().into_bytes())
+    ///         .unwrap();
+    /// });
+    ///
+    /// let (stream, _) = listener.accept().unwrap();
+    /// let mut reader = BufReader::new(stream);
+    ///
+    /// // Initial value
+    /// let framing = LengthPrefixed::new();
+    /// assert_eq!(framing.read_frame(&mut reader).unwrap(), Some(b"0".to_vec()));
+    ///
+    /// in_tx.send(12).unwrap();
+    /// assert_eq!(framing.read_frame(&mut reader).unwrap(), Some(b"12".to_vec()));
+    ///
+    /// fs::remove_file(&path).unwrap();
+    /// ```
+    ///
+    #[cfg(unix)]
+    pub fn write_unix<SA, P, Fr, F, A>(&self, mut root: SA, path: P, framing: Fr, encode: F) -> io::Result<()> where
+        SA: 'static + Signal<A>,
+        P: AsRef<Path>,
+        Fr: Framing,
+        F: 'static + Send + Fn(&A) -> Vec<u8>,
+        A: 'static + Clone + Send,
+    {
+        let stream = try!(UnixStream::connect(path));
+
+        root.init();
+
+        let sink = UnixSink::new(Box::new(root), stream, framing, encode);
+        self.runners.borrow_mut().push(Box::new(sink));
+
+        Ok(())
+    }
+}
+
+/// Determines which values are emitted by `Builder::throttle`
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ThrottleMode {
+    /// Emit the first value of each interval
+    Leading,
+    /// Emit the last value of each interval, once the interval has passed
+    Trailing,
+    /// Emit the first value of an interval immediately, and the last value
+    /// once the interval has passed if it changed in the meantime
+    Both,
+}
+
+fn eq<A: PartialEq>(l: &A, r: &A) -> bool {
+    l == r
+}
+
+fn never_eq<A>(_: &A, _: &A) -> bool {
+    false
 }
@@ -60,12 +60,18 @@ mod topology;
 mod builder;
 mod config;
 mod value;
+mod address;
 
 pub use signal_ext::SignalExt;
 pub use topology::{Topology, TopologyHandle};
-pub use builder::Builder;
+pub use builder::{Builder, ThrottleMode};
 pub use config::Config;
 pub use value::Value;
+pub use address::Address;
+pub use primitives::input::EndPolicy;
+pub use primitives::file::FileStart;
+pub use primitives::framing::{Framing, Newline, LengthPrefixed, MAX_FRAME_SIZE};
+pub use primitives::udp::{DatagramPolicy, DatagramError, DatagramCounters, DatagramCounts, MAX_DATAGRAM_SIZE};
 
 /// Container for data as it flows across the topology
 #[derive(Clone)]
@@ -166,10 +172,41 @@ pub trait Run: Send {
 ///
 pub fn spawn_topology<F>(config: Config, f: F) -> TopologyHandle where
     F: FnOnce(&Builder),
+{
+    spawn_topology_with(config, f).0
+}
+
+/// Like `spawn_topology`, but also returns the value returned by `f`
+///
+/// Useful for passing values created while building the topology, such as
+/// `Address`es, out to the caller.
+///
+/// # Example
+///
+/// ```
+/// use std::default::*;
+/// use cfrp::*;
+///
+/// let (handle, address) = spawn_topology_with(Default::default(), |t| {
+///     let (address, signal) = t.mailbox(0usize);
+///     signal.add_to(t);
+///
+///     address
+/// });
+///
+/// address.send(1).unwrap();
+/// ```
+///
+pub fn spawn_topology_with<F, R>(config: Config, f: F) -> (TopologyHandle, R) where
+    F: FnOnce(&Builder) -> R,
 {
     let builder = Builder::new(config);
-    f(&builder);
-    Topology::new(builder.inputs.into_inner(), builder.runners.into_inner()).run()
+    let r = f(&builder);
+    let handle = Topology::new(builder.inputs.into_inner(), builder.runners.into_inner())
+        .with_addresses(builder.addresses.into_inner())
+        .run();
+
+    (handle, r)
 }
 
 #[cfg(test)] 
@@ -177,10 +214,18 @@ mod test {
     extern crate env_logger;
 
     use std::default::Default;
+    use std::env;
+    use std::fs;
+    use std::fs::{File, OpenOptions};
+    use std::io;
+    use std::io::Write;
+    use std::net::{TcpStream, UdpSocket};
     use std::sync::mpsc::*;
     use std::thread;
+    use std::time::Duration;
 
     use rand;
+    use time;
 
     use super::*;
 
@@ -244,6 +289,503 @@ mod test {
         assert_eq!(out_rx.recv().unwrap(), (1 << 0) | (1 << 1));
     }
 
+    #[test]
+    fn lift4_channel_value() {
+        let (tx, rx) = sync_channel(0);
+        let (out_tx, out_rx) = channel();
+
+        spawn_topology(Default::default(), move |t| {
+            t.listen(1 << 0, rx)
+                .lift4(
+                    t.value(1 << 1),
+                    t.value(1 << 2),
+                    t.value(1 << 3),
+                    move |a,b,c,d| { out_tx.send(*a | *b | *c | *d).unwrap() }
+                )
+                .add_to(t);
+        });
+
+        // Initial value
+        assert_eq!(out_rx.recv().unwrap(), 0b00001111);
+
+        tx.send(1 << 4).unwrap();
+        assert_eq!(out_rx.recv().unwrap(), 0b00011110);
+    }
+
+    #[test]
+    fn merge_all_changed() {
+        let (a_tx, a_rx) = sync_channel(0);
+        let (b_tx, b_rx) = sync_channel(0);
+        let (out_tx, out_rx) = channel();
+
+        spawn_topology(Default::default(), move |t| {
+            let signals: Vec<Box<Signal<usize>>> = vec![
+                Box::new(t.listen(0, a_rx)),
+                Box::new(t.value(1)),
+                Box::new(t.listen(2, b_rx)),
+            ];
+
+            t.merge_all_changed(signals)
+                .lift(move |v| { out_tx.send(v).unwrap(); })
+                .add_to(t);
+        });
+
+        // Initial value
+        assert_eq!(out_rx.recv().unwrap(), vec![(0, 0), (1, 1), (2, 2)]);
+
+        b_tx.send(3).unwrap();
+        assert_eq!(out_rx.recv().unwrap(), vec![(2, 3)]);
+
+        a_tx.send(4).unwrap();
+        assert_eq!(out_rx.recv().unwrap(), vec![(0, 4)]);
+    }
+
+    #[test]
+    fn combine_values() {
+        let (tx, rx) = sync_channel(0);
+        let (out_tx, out_rx) = channel();
+
+        spawn_topology(Default::default(), move |t| {
+            let signals: Vec<Box<Signal<usize>>> = vec![
+                Box::new(t.value(0)),
+                Box::new(t.listen(1, rx)),
+            ];
+
+            t.combine_values(signals)
+                .lift(move |v| { out_tx.send(v).unwrap(); })
+                .add_to(t);
+        });
+
+        // Initial value
+        let initial = out_rx.recv().unwrap();
+        assert_eq!(initial, vec![Value::Changed(0), Value::Changed(1)]);
+
+        tx.send(2).unwrap();
+        let changed = out_rx.recv().unwrap();
+        match (&changed[0], &changed[1]) {
+            (&Value::Unchanged(0), &Value::Changed(2)) => {},
+            _ => panic!("expected only the second value to change"),
+        }
+    }
+
+    #[test]
+    fn fold3_value_channel_channel() {
+        let (a_tx, a_rx) = sync_channel(0);
+        let (b_tx, b_rx) = sync_channel(0);
+        let (out_tx, out_rx) = channel();
+
+        spawn_topology(Default::default(), move |t| {
+            t.value(1)
+                .fold3(t.listen(0, a_rx), t.listen(0, b_rx), Vec::new(), |mut changes, i, a, b| {
+                    fn changed<T>(v: Value<T>) -> bool {
+                        match v { Value::Changed(_) => true, Value::Unchanged(_) => false }
+                    }
+
+                    changes.push((changed(i), changed(a), changed(b)));
+                    changes
+                })
+                .lift(move |changes| { out_tx.send(changes).unwrap(); })
+                .add_to(t);
+        });
+
+        // Initial value
+        assert_eq!(out_rx.recv().unwrap(), vec![(true, true, true)]);
+
+        a_tx.send(1).unwrap();
+        assert_eq!(out_rx.recv().unwrap(), vec![(true, true, true), (false, true, false)]);
+
+        b_tx.send(2).unwrap();
+        assert_eq!(out_rx.recv().unwrap(), vec![(true, true, true), (false, true, false), (false, false, true)]);
+    }
+
+    #[test]
+    fn fold_all() {
+        let (a_tx, a_rx) = sync_channel(0);
+        let (b_tx, b_rx) = sync_channel(0);
+        let (out_tx, out_rx) = channel();
+
+        spawn_topology(Default::default(), move |t| {
+            t.fold_all(vec![t.listen(0, a_rx), t.listen(0, b_rx)], 0, |sum, vs| {
+                vs.into_iter().fold(sum, |sum, v| {
+                    match v {
+                        Value::Changed(i) => sum + i,
+                        Value::Unchanged(_) => sum,
+                    }
+                })
+            })
+            .lift(move |i| { out_tx.send(i).unwrap(); })
+            .add_to(t);
+        });
+
+        // Initial value
+        assert_eq!(out_rx.recv().unwrap(), 0);
+
+        a_tx.send(1).unwrap();
+        assert_eq!(out_rx.recv().unwrap(), 1);
+
+        b_tx.send(2).unwrap();
+        assert_eq!(out_rx.recv().unwrap(), 3);
+    }
+
+    #[test]
+    fn try_lift_lift2() {
+        let (tx, rx) = sync_channel(0);
+        let (out_tx, out_rx) = channel();
+
+        spawn_topology(Default::default(), move |t| {
+            let (parsed, errors) = t.listen("1", rx)
+                .try_lift(t, |s| { s.parse::<usize>().map_err(|_| s) }, 0, "");
+
+            parsed
+                .lift2(errors, move |i, e| { out_tx.send((*i, *e)).unwrap(); })
+                .add_to(t);
+        });
+
+        // Initial value
+        assert_eq!(out_rx.recv().unwrap(), (1, ""));
+
+        tx.send("foo").unwrap();
+        assert_eq!(out_rx.recv().unwrap(), (1, "foo"));
+
+        tx.send("2").unwrap();
+        assert_eq!(out_rx.recv().unwrap(), (2, "foo"));
+    }
+
+    #[test]
+    fn try_lift_errors_only() {
+        let (tx, rx) = sync_channel(0);
+        let (err_tx, err_rx) = channel();
+
+        spawn_topology(Default::default(), move |t| {
+            let (_, errors) = t.listen("1", rx)
+                .try_lift(t, |s| { s.parse::<usize>().map_err(|_| s) }, 0, "");
+
+            errors
+                .lift(move |e| { err_tx.send(e).unwrap(); })
+                .add_to(t);
+        });
+
+        // Initial value
+        assert_eq!(err_rx.recv().unwrap(), "");
+
+        tx.send("x").unwrap();
+        assert_eq!(err_rx.recv_timeout(Duration::from_millis(1000)).unwrap(), "x");
+    }
+
+    #[test]
+    fn unzip_all() {
+        let (tx, rx) = sync_channel(0);
+        let (out_tx, out_rx) = channel();
+
+        spawn_topology(Default::default(), move |t| {
+            let (l, r) = t.listen(0, rx)
+                .lift(|i| { (i, i % 2) })
+                .unzip_all(t);
+
+            l.lift2(r, move |l, r| {
+                let changed = |v: &Value<usize>| match *v { Value::Changed(_) => true, _ => false };
+                out_tx.send((changed(&l), changed(&r))).unwrap();
+            }).add_to(t);
+        });
+
+        // Initial value
+        assert_eq!(out_rx.recv().unwrap(), (true, true));
+
+        tx.send(2).unwrap();
+        assert_eq!(out_rx.recv().unwrap(), (true, true));
+    }
+
+    #[test]
+    fn listen_iter_complete() {
+        let (tx, rx) = sync_channel(0);
+        let (out_tx, out_rx) = channel();
+
+        spawn_topology(Default::default(), move |t| {
+            t.listen_iter(0, vec![1, 2].into_iter(), EndPolicy::Complete)
+                .lift2(t.listen(0, rx), move |i, j| { out_tx.send(*i + *j).unwrap(); })
+                .add_to(t);
+        });
+
+        // Initial value
+        assert_eq!(out_rx.recv().unwrap(), 0);
+        assert_eq!(out_rx.recv().unwrap(), 1);
+        assert_eq!(out_rx.recv().unwrap(), 2);
+
+        // Other inputs still run once the iterator is exhausted
+        tx.send(10).unwrap();
+        assert_eq!(out_rx.recv().unwrap(), 12);
+    }
+
+    #[test]
+    fn listen_tcp_survives_closed_connection() {
+        let (out_tx, out_rx) = channel();
+        let mut addr = None;
+
+        spawn_topology(Default::default(), |t| {
+            let (signal, bound) = t.listen_tcp("127.0.0.1:0", 0, |line| line.parse::<usize>().ok())
+                .unwrap();
+            addr = Some(bound);
+
+            signal
+                .lift(move |i| { out_tx.send(i).unwrap(); })
+                .add_to(t);
+        });
+        let addr = addr.unwrap();
+
+        // Initial value
+        assert_eq!(out_rx.recv().unwrap(), 0);
+
+        {
+            let mut stream = TcpStream::connect(addr).unwrap();
+            stream.write_all(b"1\n").unwrap();
+            assert_eq!(out_rx.recv().unwrap(), 1);
+        }
+
+        // Closing a connection doesn't stop the topology
+        let mut stream = TcpStream::connect(addr).unwrap();
+        stream.write_all(b"2\n").unwrap();
+        assert_eq!(out_rx.recv().unwrap(), 2);
+    }
+
+    #[test]
+    fn listen_tcp_ignores_invalid_utf8() {
+        let (out_tx, out_rx) = channel();
+        let mut addr = None;
+
+        spawn_topology(Default::default(), |t| {
+            let (signal, bound) = t.listen_tcp("127.0.0.1:0", 0, |line| line.parse::<usize>().ok())
+                .unwrap();
+            addr = Some(bound);
+
+            signal
+                .lift(move |i| { out_tx.send(i).unwrap(); })
+                .add_to(t);
+        });
+
+        // Initial value
+        assert_eq!(out_rx.recv().unwrap(), 0);
+
+        // The connection stays open after an invalid line
+        let mut stream = TcpStream::connect(addr.unwrap()).unwrap();
+        stream.write_all(b"\xff\n1\r\n").unwrap();
+        assert_eq!(out_rx.recv().unwrap(), 1);
+    }
+
+    #[test]
+    fn listen_tcp_stop() {
+        let (out_tx, out_rx) = channel();
+        let mut addr = None;
+
+        let mut handle = spawn_topology(Default::default(), |t| {
+            let (signal, bound) = t.listen_tcp("127.0.0.1:0", 0, |line| line.parse::<usize>().ok())
+                .unwrap();
+            addr = Some(bound);
+
+            signal
+                .lift(move |i| { out_tx.send(i).unwrap(); })
+                .add_to(t);
+        });
+        let addr = addr.unwrap();
+
+        // Initial value
+        assert_eq!(out_rx.recv().unwrap(), 0);
+
+        let mut stream = TcpStream::connect(addr).unwrap();
+        stream.write_all(b"1\n").unwrap();
+        assert_eq!(out_rx.recv().unwrap(), 1);
+
+        handle.stop();
+
+        // Open connections are ignored and the listener is closed
+        stream.write_all(b"2\n").unwrap();
+        assert!(out_rx.recv_timeout(Duration::from_millis(100)).is_err());
+
+        thread::sleep(Duration::from_millis(100));
+        assert!(TcpStream::connect(addr).is_err());
+    }
+
+    #[test]
+    fn length_prefixed_max_len() {
+        let framing = LengthPrefixed::with_max_len(2);
+
+        let mut frames = Vec::new();
+        framing.write_frame(&mut frames, b"ab").unwrap();
+        framing.write_frame(&mut frames, b"abc").unwrap();
+
+        let mut reader = &frames[..];
+        assert_eq!(framing.read_frame(&mut reader).unwrap(), Some(b"ab".to_vec()));
+        assert_eq!(framing.read_frame(&mut reader).unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn newline_max_len() {
+        let framing = Newline::with_max_len(2);
+
+        let mut reader = &b"ab\nabc\n"[..];
+        assert_eq!(framing.read_frame(&mut reader).unwrap(), Some(b"ab".to_vec()));
+        assert_eq!(framing.read_frame(&mut reader).unwrap_err().kind(), io::ErrorKind::InvalidData);
+
+        // A final frame without a terminator is still returned
+        let mut reader = &b"ab"[..];
+        assert_eq!(framing.read_frame(&mut reader).unwrap(), Some(b"ab".to_vec()));
+        assert_eq!(framing.read_frame(&mut reader).unwrap(), None);
+    }
+
+    #[test]
+    fn listen_udp_stop() {
+        let (out_tx, out_rx) = channel();
+        let mut addr = None;
+
+        let mut handle = spawn_topology(Default::default(), |t| {
+            let (signal, bound) = t.listen_udp("127.0.0.1:0", 0, |bytes, _| Some(bytes.len()))
+                .unwrap();
+            addr = Some(bound);
+
+            signal
+                .lift(move |i| { out_tx.send(i).unwrap(); })
+                .add_to(t);
+        });
+        let addr = addr.unwrap();
+
+        // Initial value
+        assert_eq!(out_rx.recv().unwrap(), 0);
+
+        let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        socket.send_to(b"a", addr).unwrap();
+        assert_eq!(out_rx.recv().unwrap(), 1);
+
+        handle.stop();
+
+        // Datagrams received after stopping are ignored
+        socket.send_to(b"ab", addr).unwrap();
+        assert!(out_rx.recv_timeout(Duration::from_millis(100)).is_err());
+    }
+
+    #[test]
+    #[cfg(unix)] // Replacement is detected by inode
+    fn listen_file_truncate_and_rotate() {
+        let dir = env::temp_dir();
+        let path = dir.join("cfrp-listen-file-rotate.log");
+        let rotated = dir.join("cfrp-listen-file-rotate.log.1");
+        File::create(&path).unwrap().write_all(b"1\n").unwrap();
+        let (out_tx, out_rx) = channel();
+
+        let log_path = path.clone();
+        spawn_topology(Default::default(), move |t| {
+            t.listen_file(log_path, 0, FileStart::End, time::Duration::milliseconds(10), |line| {
+                line.parse::<usize>().ok()
+            })
+                .lift(move |i| { out_tx.send(i).unwrap(); })
+                .add_to(t);
+        });
+
+        // Initial value, existing lines are skipped
+        assert_eq!(out_rx.recv().unwrap(), 0);
+
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(b"2\n").unwrap();
+        assert_eq!(out_rx.recv().unwrap(), 2);
+
+        // Truncated
+        File::create(&path).unwrap().write_all(b"3\n").unwrap();
+        assert_eq!(out_rx.recv().unwrap(), 3);
+
+        // Rotated
+        fs::rename(&path, &rotated).unwrap();
+        File::create(&path).unwrap().write_all(b"4\n").unwrap();
+        assert_eq!(out_rx.recv().unwrap(), 4);
+
+        fs::remove_file(&path).unwrap();
+        fs::remove_file(&rotated).unwrap();
+    }
+
+    #[test]
+    fn window_snapshots_are_unchanged() {
+        let (tx, rx) = sync_channel(0);
+        let (out_tx, out_rx) = channel();
+
+        spawn_topology(Default::default(), move |t| {
+            t.listen(0, rx)
+                .window(2)
+                .lift(move |w| { out_tx.send(w).unwrap(); })
+                .add_to(t);
+        });
+
+        // Initial value
+        let first = out_rx.recv().unwrap();
+
+        tx.send(1).unwrap();
+        let second = out_rx.recv().unwrap();
+
+        tx.send(2).unwrap();
+        let third = out_rx.recv().unwrap();
+
+        // Held snapshots aren't affected by later values
+        assert_eq!(first.into_iter().collect::<Vec<_>>(), vec![0]);
+        assert_eq!(second.into_iter().collect::<Vec<_>>(), vec![0, 1]);
+        assert_eq!(third.into_iter().collect::<Vec<_>>(), vec![1, 2]);
+    }
+
+    #[test]
+    fn switch_by_unknown_key_keeps_selection() {
+        let (key_tx, key_rx) = sync_channel(0);
+        let (a_tx, a_rx) = sync_channel(0);
+        let (_b_tx, b_rx) = sync_channel::<usize>(0);
+        let (out_tx, out_rx) = channel();
+
+        spawn_topology(Default::default(), move |t| {
+            t.listen(0, key_rx)
+                .switch_by(
+                    vec![t.listen(0, a_rx), t.listen(10, b_rx)],
+                    |k: &usize| if *k < 2 { Some(*k) } else { None },
+                )
+                .lift(move |i| { out_tx.send(i).unwrap(); })
+                .add_to(t);
+        });
+
+        // Initial value
+        assert_eq!(out_rx.recv().unwrap(), 0);
+
+        a_tx.send(5).unwrap();
+        assert_eq!(out_rx.recv().unwrap(), 5);
+
+        // Unknown keys don't re-emit the selected branch's value
+        key_tx.send(7).unwrap();
+        key_tx.send(1).unwrap();
+        assert_eq!(out_rx.recv().unwrap(), 10);
+    }
+
+    #[test]
+    fn mailbox_returned_from_builder() {
+        let (out_tx, out_rx) = channel();
+
+        let (_, address) = spawn_topology_with(Default::default(), move |t| {
+            let (address, signal) = t.mailbox(0);
+
+            signal
+                .lift(move |i| { out_tx.send(i).unwrap(); })
+                .add_to(t);
+
+            address
+        });
+
+        // Initial value
+        assert_eq!(out_rx.recv().unwrap(), 0);
+
+        let sender = address.clone();
+        thread::spawn(move || { sender.send(1).unwrap(); }).join().unwrap();
+        assert_eq!(out_rx.recv().unwrap(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "already in use")]
+    fn named_mailbox_duplicate_name() {
+        spawn_topology(Default::default(), |t| {
+            t.named_mailbox("input", 0).add_to(t);
+            t.named_mailbox("input", 0).add_to(t);
+        });
+    }
+
     #[test]
     fn async_sends() {
         let (tx, rx) = sync_channel(0);
@@ -296,6 +838,87 @@ mod test {
         assert_eq!(out_rx.recv().unwrap(), (1 << 2) | (1 << 3));
     }
 
+    #[test]
+    fn delay_stop_drops_pending() {
+        let (tx, rx) = channel();
+        let (out_tx, out_rx) = channel();
+
+        let mut handle = spawn_topology(Default::default(), move |t| {
+            t.listen(0, rx)
+                .delay(t, time::Duration::milliseconds(100))
+                .lift(move |i| { out_tx.send(i).unwrap(); })
+                .add_to(t);
+        });
+
+        // Initial value
+        assert_eq!(out_rx.recv().unwrap(), 0);
+
+        tx.send(1).unwrap();
+        thread::sleep_ms(20);
+        handle.stop();
+
+        thread::sleep_ms(200);
+        assert!(out_rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn debounce_stop_drops_pending() {
+        let (tx, rx) = channel();
+        let (out_tx, out_rx) = channel();
+
+        let mut handle = spawn_topology(Default::default(), move |t| {
+            t.listen(0, rx)
+                .debounce(t, time::Duration::milliseconds(100))
+                .lift(move |i| { out_tx.send(i).unwrap(); })
+                .add_to(t);
+        });
+
+        // Initial value
+        assert_eq!(out_rx.recv().unwrap(), 0);
+
+        tx.send(1).unwrap();
+        thread::sleep_ms(20);
+        handle.stop();
+
+        thread::sleep_ms(200);
+        assert!(out_rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn throttle_stop_drops_pending() {
+        let (tx, rx) = channel();
+        let (out_tx, out_rx) = channel();
+
+        let mut handle = spawn_topology(Default::default(), move |t| {
+            t.listen(0, rx)
+                .throttle(t, time::Duration::milliseconds(100), ThrottleMode::Trailing)
+                .lift(move |i| { out_tx.send(i).unwrap(); })
+                .add_to(t);
+        });
+
+        // Initial value
+        assert_eq!(out_rx.recv().unwrap(), 0);
+
+        tx.send(1).unwrap();
+        thread::sleep_ms(20);
+        handle.stop();
+
+        thread::sleep_ms(200);
+        assert!(out_rx.try_recv().is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "must not be negative")]
+    fn debounce_negative_duration() {
+        let (_tx, rx) = channel::<usize>();
+
+        spawn_topology(Default::default(), move |t| {
+            t.listen(0, rx)
+                .debounce(t, time::Duration::milliseconds(-1))
+                .add_to(t);
+        });
+    }
+
     #[test]
     fn branch() {
         let (tx, rx) = channel();
@@ -0,0 +1,66 @@
+use std::time::Instant;
+use std::sync::mpsc::*;
+
+use super::super::{Event, Signal, SignalType, Push, Run};
+
+// Like `Async`, but tags each value with the time it was received so it can
+// be released by a `DelayInput` once the delay has passed, and sends to an
+// unbounded channel so it never blocks on the input it feeds.
+//
+pub struct Delay<A> {
+    parent: Box<Signal<A>>,
+    tx: Sender<(Instant, A)>,
+}
+
+impl<A> Delay<A> {
+    pub fn new(parent: Box<Signal<A>>, tx: Sender<(Instant, A)>) -> Delay<A> {
+        Delay {
+            parent: parent,
+            tx: tx,
+        }
+    }
+}
+
+impl<A> Run for Delay<A> where
+    A: 'static + Send + Clone
+{
+    fn run(self: Box<Self>) {
+        debug!("Delay::run");
+
+        let inner = *self;
+        let Delay { parent, tx } = inner;
+
+        match parent.initial() {
+            SignalType::Constant(_) => return,
+            SignalType::Dynamic(_) => {
+                parent.push_to(Some(Box::new(DelayPusher {tx: tx})));
+            },
+        }
+    }
+}
+
+struct DelayPusher<A> {
+    tx: Sender<(Instant, A)>,
+}
+
+impl<A> Push<A> for DelayPusher<A> where
+    A: 'static + Clone + Send,
+{
+    fn push(&mut self, event: Event<A>) {
+        match event {
+            Event::Changed(a) => {
+                debug!("Delay handling Event Changed - pushing to channel");
+                match self.tx.send((Instant::now(), a)) {
+                    // We can't really terminate a child process, so just ignore errors...
+                    _ => {},
+                }
+            },
+            Event::Unchanged => {
+                debug!("Delay handling Event Unchanged - doing nothing");
+            },
+            Event::Exit => {
+                debug!("Delay handling Event Exit");
+            }
+        }
+    }
+}
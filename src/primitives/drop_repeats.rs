@@ -0,0 +1,116 @@
+use super::super::{Event, Signal, SignalExt, SignalType, Push, Config};
+
+/// The result of a `drop_repeats` or `drop_repeats_by` operation
+///
+pub struct DropRepeatsSignal<F, A, K> where
+    F: 'static + Send + Fn(&A) -> K,
+    A: 'static + Send + Clone,
+    K: 'static + Send + PartialEq,
+{
+    config: Config,
+    parent: Box<Signal<A>>,
+    f: F,
+    initial: SignalType<A>,
+}
+
+impl<F, A, K> DropRepeatsSignal<F, A, K> where
+    F: 'static + Send + Fn(&A) -> K,
+    A: 'static + Send + Clone,
+    K: 'static + Send + PartialEq,
+{
+    pub fn new(config: Config, parent: Box<Signal<A>>, f: F) -> Self {
+        let initial = parent.initial();
+
+        DropRepeatsSignal {
+            config: config,
+            parent: parent,
+            f: f,
+            initial: initial,
+        }
+    }
+}
+
+impl<F, A, K> Signal<A> for DropRepeatsSignal<F, A, K> where
+    F: 'static + Send + Fn(&A) -> K,
+    A: 'static + Send + Clone,
+    K: 'static + Send + PartialEq,
+{
+    fn config(&self) -> Config {
+        self.config.clone()
+    }
+
+    fn initial(&self) -> SignalType<A> {
+        self.initial.clone()
+    }
+
+    fn push_to(self: Box<Self>, target: Option<Box<Push<A>>>) {
+        let inner = *self;
+        let DropRepeatsSignal { config: _, parent, f, initial } = inner;
+
+        let last = f(&initial.unwrap());
+
+        debug!("SETUP: DropRepeats pushing to parent");
+        parent.push_to(
+            Some(
+                Box::new(
+                    DropRepeatsPusher {
+                        child: target,
+                        f: f,
+                        last: last,
+                    }
+                )
+            )
+        );
+    }
+}
+impl<F, A, K> SignalExt<A> for DropRepeatsSignal<F, A, K> where
+    F: 'static + Send + Fn(&A) -> K,
+    A: 'static + Send + Clone,
+    K: 'static + Send + PartialEq,
+{}
+
+struct DropRepeatsPusher<F, A, K> where
+    F: 'static + Send + Fn(&A) -> K,
+    A: 'static + Send + Clone,
+    K: 'static + Send + PartialEq,
+{
+    child: Option<Box<Push<A>>>,
+    f: F,
+    last: K,
+}
+
+impl<F, A, K> Push<A> for DropRepeatsPusher<F, A, K> where
+    F: 'static + Send + Fn(&A) -> K,
+    A: 'static + Send + Clone,
+    K: 'static + Send + PartialEq,
+{
+    fn push(&mut self, event: Event<A>) {
+        let out = match event {
+            Event::Changed(a) => {
+                let k = (self.f)(&a);
+
+                if k == self.last {
+                    info!("RUN: DropRepeatsPusher handling repeated Event::Changed");
+                    Event::Unchanged
+                } else {
+                    info!("RUN: DropRepeatsPusher handling Event::Changed");
+                    self.last = k;
+                    Event::Changed(a)
+                }
+            },
+            Event::Unchanged => {
+                info!("RUN: DropRepeatsPusher handling Event::Unchanged");
+                Event::Unchanged
+            },
+            Event::Exit => {
+                info!("RUN: DropRepeatsPusher handling Event::Exit");
+                Event::Exit
+            },
+        };
+
+        match self.child {
+            Some(ref mut t) => t.push(out),
+            None => {},
+        }
+    }
+}
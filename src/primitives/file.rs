@@ -0,0 +1,232 @@
+use std::fs;
+use std::fs::File;
+use std::thread;
+use std::io::{BufRead, BufReader, Seek, SeekFrom};
+#[cfg(unix)]
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use std::sync::*;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::*;
+
+use super::super::Event;
+use super::input::{RunInput, NoOp, send_changed};
+
+/// Determines where a file input starts reading
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FileStart {
+    /// Read the file's existing contents
+    Beginning,
+    /// Only read lines appended after the input starts
+    End,
+}
+
+/// Follows a file by name, like `tail -F`
+///
+/// The file is polled every `interval` for new lines.  If the file is
+/// truncated it's read again from the beginning, and if it's replaced (ie
+/// its inode changes) the new file is read from the beginning.  `start` only
+/// applies to the file which exists when the input is created.  Replacement
+/// is only detected on Unix, where inodes are available.
+///
+pub struct FileInput<F, A> {
+    path: PathBuf,
+    tail: Option<Tail>,
+    interval: Duration,
+    parse: F,
+    tx: SyncSender<Event<A>>,
+    stopped: Arc<AtomicBool>,
+}
+
+impl<F, A> FileInput<F, A> {
+    pub fn new(path: PathBuf, start: FileStart, interval: Duration, parse: F, tx: SyncSender<Event<A>>) -> FileInput<F, A> {
+        // Open the file now so `FileStart::End` doesn't depend on when the
+        // input's thread gets to run
+        let tail = Tail::open(&path, start == FileStart::End);
+
+        FileInput {
+            path: path,
+            tail: tail,
+            interval: interval,
+            parse: parse,
+            tx: tx,
+            stopped: Arc::new(AtomicBool::new(false)),
+        }
+    }
+}
+
+impl<F, A> RunInput for FileInput<F, A> where
+    F: 'static + Send + Fn(&str) -> Option<A>,
+    A: 'static + Send + Clone,
+{
+    fn boxed_no_op(&self) -> Box<NoOp> {
+        Box::new(FileNoOp { tx: self.tx.clone(), stopped: self.stopped.clone() })
+    }
+
+    fn run(self: Box<Self>, idx: usize, txs: Arc<Mutex<Vec<Box<NoOp>>>>) {
+        debug!("SETUP: running FileInput");
+        let inner = *self;
+        let FileInput {path, mut tail, interval, parse, tx, stopped} = inner;
+
+        let send_line = |line: Vec<u8>| {
+            match String::from_utf8(line) {
+                Ok(line) => {
+                    match parse(&line) {
+                        Some(a) => {
+                            info!("RUN: FileInput received data, sending");
+                            send_changed(&tx, idx, &txs, a)
+                        },
+                        None => {
+                            info!("RUN: FileInput failed to parse line, ignoring");
+                            true
+                        },
+                    }
+                },
+                Err(_) => {
+                    info!("RUN: FileInput read invalid UTF-8, ignoring");
+                    true
+                },
+            }
+        };
+
+        let mut partial = Vec::new();
+
+        loop {
+            if stopped.load(Ordering::SeqCst) {
+                info!("RUN: FileInput stopped, exiting");
+                return
+            }
+
+            let rotated = match tail {
+                Some(ref mut t) => {
+                    if !t.read_lines(&mut partial, &send_line) { return }
+
+                    match fs::metadata(&path) {
+                        Ok(ref m) if ino(m) == t.ino => {
+                            if m.len() < t.pos {
+                                info!("RUN: FileInput file truncated, reading from beginning");
+                                t.rewind();
+                                partial.clear();
+                            }
+                            false
+                        },
+                        // Lines may have been written before the file was
+                        // replaced, so finish reading it first
+                        _ => {
+                            info!("RUN: FileInput file replaced, reopening");
+                            if !t.read_lines(&mut partial, &send_line) { return }
+                            if !partial.is_empty() && !send_line(partial.split_off(0)) { return }
+                            true
+                        },
+                    }
+                },
+                None => false,
+            };
+
+            if rotated || tail.is_none() {
+                tail = Tail::open(&path, false);
+            }
+
+            if !rotated {
+                thread::sleep(interval);
+            }
+        }
+    }
+}
+
+// Identifies the file behind a path, so we can tell when it's been replaced
+//
+#[cfg(unix)]
+fn ino(m: &fs::Metadata) -> u64 {
+    m.ino()
+}
+
+// Without inodes every file looks the same, so replacement isn't detected
+//
+#[cfg(not(unix))]
+fn ino(_: &fs::Metadata) -> u64 {
+    0
+}
+
+struct Tail {
+    reader: BufReader<File>,
+    ino: u64,
+    pos: u64,
+}
+
+impl Tail {
+    // Returns `None` if the file doesn't exist yet
+    fn open(path: &Path, at_end: bool) -> Option<Tail> {
+        let mut file = match File::open(path) {
+            Ok(file) => file,
+            Err(_) => return None,
+        };
+
+        let ino = match file.metadata() {
+            Ok(ref m) => ino(m),
+            Err(_) => return None,
+        };
+
+        let pos = if at_end {
+            match file.seek(SeekFrom::End(0)) {
+                Ok(pos) => pos,
+                Err(_) => return None,
+            }
+        } else {
+            0
+        };
+
+        Some(Tail { reader: BufReader::new(file), ino: ino, pos: pos })
+    }
+
+    fn rewind(&mut self) {
+        match self.reader.seek(SeekFrom::Start(0)) {
+            Ok(_) => self.pos = 0,
+            Err(e) => info!("RUN: FileInput failed to rewind: {}", e),
+        }
+    }
+
+    // Passes each complete line to `f`, keeping any incomplete line in
+    // `partial`.  Returns `false` if `f` does
+    fn read_lines<F>(&mut self, partial: &mut Vec<u8>, f: &F) -> bool where
+        F: Fn(Vec<u8>) -> bool,
+    {
+        loop {
+            match self.reader.read_until(b'\n', partial) {
+                Ok(0) => return true,
+                Ok(n) => {
+                    self.pos += n as u64;
+
+                    if partial.last() == Some(&b'\n') {
+                        partial.pop();
+                        if !f(partial.split_off(0)) { return false }
+                    }
+                },
+                Err(e) => {
+                    info!("RUN: FileInput failed to read: {}", e);
+                    return true
+                },
+            }
+        }
+    }
+}
+
+struct FileNoOp<A> {
+    tx: SyncSender<Event<A>>,
+    stopped: Arc<AtomicBool>,
+}
+
+impl<A> NoOp for FileNoOp<A> where
+    A: Send
+{
+    fn send_no_change(&mut self) -> bool {
+        self.tx.send_no_change()
+    }
+
+    fn send_exit(&self) {
+        self.stopped.store(true, Ordering::SeqCst);
+        self.tx.send_exit()
+    }
+}
@@ -0,0 +1,119 @@
+use std::marker::*;
+
+use super::super::{Event, Signal, SignalExt, SignalType, Push, Config};
+
+/// The result of a `filter_map` operation
+///
+pub struct FilterMapSignal<F, A, B> where
+    F: 'static + Send + Fn(A) -> Option<B>,
+    A: 'static + Send + Clone,
+    B: 'static + Send + Clone,
+{
+    config: Config,
+    parent: Box<Signal<A>>,
+    f: F,
+    initial: SignalType<B>,
+}
+
+impl<F, A, B> FilterMapSignal<F, A, B> where
+    F: 'static + Send + Fn(A) -> Option<B>,
+    A: 'static + Send + Clone,
+    B: 'static + Send + Clone,
+{
+    pub fn new(config: Config, parent: Box<Signal<A>>, f: F, default: B) -> Self {
+        let initial = match parent.initial() {
+            SignalType::Constant(a) => SignalType::Constant(f(a).unwrap_or(default)),
+            SignalType::Dynamic(a) => SignalType::Dynamic(f(a).unwrap_or(default)),
+        };
+
+        FilterMapSignal {
+            config: config,
+            parent: parent,
+            f: f,
+            initial: initial,
+        }
+    }
+}
+
+impl<F, A, B> Signal<B> for FilterMapSignal<F, A, B> where
+    F: 'static + Send + Fn(A) -> Option<B>,
+    A: 'static + Send + Clone,
+    B: 'static + Send + Clone,
+{
+    fn config(&self) -> Config {
+        self.config.clone()
+    }
+
+    fn initial(&self) -> SignalType<B> {
+        self.initial.clone()
+    }
+
+    fn push_to(self: Box<Self>, target: Option<Box<Push<B>>>) {
+        let inner = *self;
+        let FilterMapSignal { config: _, parent, f, initial: _ } = inner;
+
+        debug!("SETUP: FilterMap pushing to parent");
+        parent.push_to(
+            Some(
+                Box::new(
+                    FilterMapPusher {
+                        child: target,
+                        f: f,
+                        marker: PhantomData,
+                    }
+                )
+            )
+        );
+    }
+}
+impl<F, A, B> SignalExt<B> for FilterMapSignal<F, A, B> where
+    F: 'static + Send + Fn(A) -> Option<B>,
+    A: 'static + Send + Clone,
+    B: 'static + Send + Clone,
+{}
+
+struct FilterMapPusher<F, A, B> where
+    F: 'static + Send + Fn(A) -> Option<B>,
+    A: 'static + Send + Clone,
+    B: 'static + Send + Clone,
+{
+    child: Option<Box<Push<B>>>,
+    f: F,
+    marker: PhantomData<A>,
+}
+
+impl<F, A, B> Push<A> for FilterMapPusher<F, A, B> where
+    F: 'static + Send + Fn(A) -> Option<B>,
+    A: 'static + Send + Clone,
+    B: 'static + Send + Clone,
+{
+    fn push(&mut self, event: Event<A>) {
+        let out = match event {
+            Event::Changed(a) => {
+                match (self.f)(a) {
+                    Some(b) => {
+                        info!("RUN: FilterMapPusher handling Event::Changed");
+                        Event::Changed(b)
+                    },
+                    None => {
+                        info!("RUN: FilterMapPusher dropping Event::Changed");
+                        Event::Unchanged
+                    },
+                }
+            },
+            Event::Unchanged => {
+                info!("RUN: FilterMapPusher handling Event::Unchanged");
+                Event::Unchanged
+            },
+            Event::Exit => {
+                info!("RUN: FilterMapPusher handling Event::Exit");
+                Event::Exit
+            },
+        };
+
+        match self.child {
+            Some(ref mut t) => t.push(out),
+            None => {},
+        }
+    }
+}
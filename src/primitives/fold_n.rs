@@ -0,0 +1,291 @@
+use std::thread;
+use std::sync::mpsc::*;
+
+use super::super::{Value, Event, Signal, SignalExt, SignalType, Push, Config};
+use super::lift2::InputPusher;
+
+// Each `FoldNSignal` merges its parents exactly like the matching
+// `LiftNSignal`, but instead of mapping each tick's values to an output it
+// folds them into a state.  The state lives in the merge loop, so nothing is
+// boxed or cloned apart from the value which is emitted.
+//
+// The macro takes the name of the signal, then for each parent its type
+// parameter, field name, a name for its event receiver and a name for its
+// cached value.  The final type parameter is the state type.
+//
+macro_rules! fold_n {
+    ($name:ident, $( ($t:ident, $parent:ident, $rx:ident, $last:ident) ),+ ; $state:ident) => {
+        /// The result of a `foldN` operation
+        ///
+        pub struct $name<F, $($t,)+ $state> where
+            F: 'static + Send + Fn($state, $(Value<$t>),+) -> $state,
+            $($t: 'static + Send + Clone,)+
+            $state: 'static + Send + Clone,
+        {
+            config: Config,
+            $($parent: Box<Signal<$t>>,)+
+            f: F,
+            state: SignalType<$state>,
+        }
+
+        impl<F, $($t,)+ $state> $name<F, $($t,)+ $state> where
+            F: 'static + Send + Fn($state, $(Value<$t>),+) -> $state,
+            $($t: 'static + Send + Clone,)+
+            $state: 'static + Send + Clone,
+        {
+            pub fn new(config: Config, $($parent: Box<Signal<$t>>,)+ initial: $state, f: F) -> Self {
+                let mut all_constant = true;
+                $(
+                    let $last = match $parent.initial() {
+                        SignalType::Constant(v) => v,
+                        SignalType::Dynamic(v) => {
+                            all_constant = false;
+                            v
+                        },
+                    };
+                )+
+
+                let s = f(initial, $(Value::Changed($last)),+);
+                let state = if all_constant {
+                    SignalType::Constant(s)
+                } else {
+                    SignalType::Dynamic(s)
+                };
+
+                $name {
+                    config: config,
+                    $($parent: $parent,)+
+                    f: f,
+                    state: state,
+                }
+            }
+        }
+
+        impl<F, $($t,)+ $state> Signal<$state> for $name<F, $($t,)+ $state> where
+            F: 'static + Send + Fn($state, $(Value<$t>),+) -> $state,
+            $($t: 'static + Send + Clone,)+
+            $state: 'static + Send + Clone,
+        {
+            fn config(&self) -> Config {
+                self.config.clone()
+            }
+
+            fn initial(&self) -> SignalType<$state> {
+                self.state.clone()
+            }
+
+            fn push_to(self: Box<Self>, mut target: Option<Box<Push<$state>>>) {
+                let inner = *self;
+                let $name {config, $($parent,)+ f, state} = inner;
+
+                let mut s = match state {
+                    SignalType::Constant(s) => s,
+                    SignalType::Dynamic(s) => s,
+                };
+
+                $(
+                    let (mut $last, $rx) = match $parent.initial() {
+                        SignalType::Constant(v) => (SignalType::Constant(v), None),
+                        SignalType::Dynamic(v) => {
+                            let (tx, rx) = sync_channel(config.buffer_size.clone());
+                            thread::spawn(move || {
+                                $parent.push_to(Some(Box::new(InputPusher::new(tx))));
+                            });
+
+                            (SignalType::Dynamic(v), Some(rx))
+                        },
+                    };
+                )+
+
+                loop {
+                    let mut any_changed = false;
+
+                    $(
+                        let $parent = match (&mut $last, &$rx) {
+                            (&mut SignalType::Constant(ref v), _) => Value::Unchanged(v.clone()),
+                            (&mut SignalType::Dynamic(ref mut last), &Some(ref rx)) => {
+                                match rx.recv() {
+                                    Ok(Event::Changed(v)) => {
+                                        info!("RUN: {} using changed value", stringify!($name));
+                                        any_changed = true;
+                                        *last = v.clone();
+                                        Value::Changed(v)
+                                    },
+                                    Ok(Event::Unchanged) => {
+                                        info!("RUN: {} using cached value", stringify!($name));
+                                        Value::Unchanged(last.clone())
+                                    },
+                                    Ok(Event::Exit) => return,
+                                    Err(_) => return,
+                                }
+                            },
+                            (&mut SignalType::Dynamic(_), &None) => unreachable!(),
+                        };
+                    )+
+
+                    let out = if any_changed {
+                        s = f(s, $($parent),+);
+                        Event::Changed(s.clone())
+                    } else {
+                        Event::Unchanged
+                    };
+
+                    match target {
+                        Some(ref mut t) => t.push(out),
+                        None => {},
+                    }
+                }
+            }
+        }
+        impl<F, $($t,)+ $state> SignalExt<$state> for $name<F, $($t,)+ $state> where
+            F: 'static + Send + Fn($state, $(Value<$t>),+) -> $state,
+            $($t: 'static + Send + Clone,)+
+            $state: 'static + Send + Clone,
+        {}
+    }
+}
+
+fold_n!(Fold2Signal,
+    (A, a, a_rx, last_a),
+    (B, b, b_rx, last_b)
+    ; S);
+
+fold_n!(Fold3Signal,
+    (A, a, a_rx, last_a),
+    (B, b, b_rx, last_b),
+    (C, c, c_rx, last_c)
+    ; S);
+
+/// The result of folding a collection of same-typed signals
+///
+/// Merges its parents like `LiftAllSignal`, but folds each tick's values
+/// into a state instead of mapping them.
+///
+pub struct FoldAllSignal<F, A, S> where
+    F: 'static + Send + Fn(S, Vec<Value<A>>) -> S,
+    A: 'static + Send + Clone,
+    S: 'static + Send + Clone,
+{
+    config: Config,
+    parents: Vec<Box<Signal<A>>>,
+    f: F,
+    state: SignalType<S>,
+}
+
+impl<F, A, S> FoldAllSignal<F, A, S> where
+    F: 'static + Send + Fn(S, Vec<Value<A>>) -> S,
+    A: 'static + Send + Clone,
+    S: 'static + Send + Clone,
+{
+    pub fn new(config: Config, parents: Vec<Box<Signal<A>>>, initial: S, f: F) -> Self {
+        let mut all_constant = true;
+        let values = parents.iter().map(|parent| {
+            match parent.initial() {
+                SignalType::Constant(a) => Value::Changed(a),
+                SignalType::Dynamic(a) => {
+                    all_constant = false;
+                    Value::Changed(a)
+                },
+            }
+        }).collect();
+
+        let s = f(initial, values);
+        let state = if all_constant {
+            SignalType::Constant(s)
+        } else {
+            SignalType::Dynamic(s)
+        };
+
+        FoldAllSignal {
+            config: config,
+            parents: parents,
+            f: f,
+            state: state,
+        }
+    }
+}
+
+impl<F, A, S> Signal<S> for FoldAllSignal<F, A, S> where
+    F: 'static + Send + Fn(S, Vec<Value<A>>) -> S,
+    A: 'static + Send + Clone,
+    S: 'static + Send + Clone,
+{
+    fn config(&self) -> Config {
+        self.config.clone()
+    }
+
+    fn initial(&self) -> SignalType<S> {
+        self.state.clone()
+    }
+
+    fn push_to(self: Box<Self>, mut target: Option<Box<Push<S>>>) {
+        let inner = *self;
+        let FoldAllSignal {config, parents, f, state} = inner;
+
+        let mut s = match state {
+            SignalType::Constant(s) => s,
+            SignalType::Dynamic(s) => s,
+        };
+
+        let mut inputs = parents.into_iter().map(|parent| {
+            match parent.initial() {
+                SignalType::Constant(a) => (SignalType::Constant(a), None),
+                SignalType::Dynamic(a) => {
+                    let (tx, rx) = sync_channel(config.buffer_size.clone());
+                    thread::spawn(move || {
+                        parent.push_to(Some(Box::new(InputPusher::new(tx))));
+                    });
+
+                    (SignalType::Dynamic(a), Some(rx))
+                },
+            }
+        }).collect::<Vec<(SignalType<A>, Option<Receiver<Event<A>>>)>>();
+
+        loop {
+            let mut any_changed = false;
+            let mut values = Vec::with_capacity(inputs.len());
+
+            for &mut (ref mut last, ref rx) in inputs.iter_mut() {
+                let v = match (last, rx) {
+                    (&mut SignalType::Constant(ref a), _) => Value::Unchanged(a.clone()),
+                    (&mut SignalType::Dynamic(ref mut last), &Some(ref rx)) => {
+                        match rx.recv() {
+                            Ok(Event::Changed(a)) => {
+                                info!("RUN: FoldAll using changed value");
+                                any_changed = true;
+                                *last = a.clone();
+                                Value::Changed(a)
+                            },
+                            Ok(Event::Unchanged) => {
+                                info!("RUN: FoldAll using cached value");
+                                Value::Unchanged(last.clone())
+                            },
+                            Ok(Event::Exit) => return,
+                            Err(_) => return,
+                        }
+                    },
+                    (&mut SignalType::Dynamic(_), &None) => unreachable!(),
+                };
+
+                values.push(v);
+            }
+
+            let out = if any_changed {
+                s = f(s, values);
+                Event::Changed(s.clone())
+            } else {
+                Event::Unchanged
+            };
+
+            match target {
+                Some(ref mut t) => t.push(out),
+                None => {},
+            }
+        }
+    }
+}
+impl<F, A, S> SignalExt<S> for FoldAllSignal<F, A, S> where
+    F: 'static + Send + Fn(S, Vec<Value<A>>) -> S,
+    A: 'static + Send + Clone,
+    S: 'static + Send + Clone,
+{}
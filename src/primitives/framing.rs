@@ -0,0 +1,138 @@
+use std::io;
+use std::io::{BufRead, Read, Write};
+
+/// Splits a byte stream into frames
+///
+pub trait Framing: 'static + Send + Sync {
+    /// Reads the next frame from `reader`, returning `None` at end of stream
+    ///
+    fn read_frame<R: BufRead>(&self, reader: &mut R) -> io::Result<Option<Vec<u8>>>;
+
+    /// Writes `frame` to `writer`
+    ///
+    fn write_frame<W: Write>(&self, writer: &mut W, frame: &[u8]) -> io::Result<()>;
+}
+
+/// The largest frame `Newline` and `LengthPrefixed` read by default
+///
+pub const MAX_FRAME_SIZE: usize = 16 * 1024 * 1024;
+
+/// Frames terminated by `\n`
+///
+/// The terminator isn't included in frames that are read, and a final frame
+/// without a terminator is still returned.  Frames longer than the maximum
+/// length are rejected with `io::ErrorKind::InvalidData` rather than read.
+///
+#[derive(Clone, Copy, Debug)]
+pub struct Newline {
+    max_len: usize,
+}
+
+impl Newline {
+    /// Reads frames of up to `MAX_FRAME_SIZE` bytes
+    ///
+    pub fn new() -> Newline {
+        Default::default()
+    }
+
+    /// Reads frames of up to `max_len` bytes
+    ///
+    pub fn with_max_len(max_len: usize) -> Newline {
+        Newline { max_len: max_len }
+    }
+}
+
+impl Default for Newline {
+    fn default() -> Newline {
+        Newline::with_max_len(MAX_FRAME_SIZE)
+    }
+}
+
+impl Framing for Newline {
+    fn read_frame<R: BufRead>(&self, reader: &mut R) -> io::Result<Option<Vec<u8>>> {
+        let mut frame = Vec::new();
+
+        // Leave room for the terminator, anything longer is rejected
+        let limit = self.max_len as u64 + 1;
+        if try!(reader.by_ref().take(limit).read_until(b'\n', &mut frame)) == 0 {
+            return Ok(None)
+        }
+
+        if frame.last() == Some(&b'\n') {
+            frame.pop();
+        } else if frame.len() > self.max_len {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "frame too long"))
+        }
+
+        Ok(Some(frame))
+    }
+
+    fn write_frame<W: Write>(&self, writer: &mut W, frame: &[u8]) -> io::Result<()> {
+        try!(writer.write_all(frame));
+        writer.write_all(b"\n")
+    }
+}
+
+/// Frames preceded by their length as a big-endian `u32`
+///
+/// The length comes from the peer, so frames longer than the maximum length
+/// are rejected with `io::ErrorKind::InvalidData` rather than read.
+///
+#[derive(Clone, Copy, Debug)]
+pub struct LengthPrefixed {
+    max_len: usize,
+}
+
+impl LengthPrefixed {
+    /// Reads frames of up to `MAX_FRAME_SIZE` bytes
+    ///
+    pub fn new() -> LengthPrefixed {
+        Default::default()
+    }
+
+    /// Reads frames of up to `max_len` bytes
+    ///
+    pub fn with_max_len(max_len: usize) -> LengthPrefixed {
+        LengthPrefixed { max_len: max_len }
+    }
+}
+
+impl Default for LengthPrefixed {
+    fn default() -> LengthPrefixed {
+        LengthPrefixed::with_max_len(MAX_FRAME_SIZE)
+    }
+}
+
+impl Framing for LengthPrefixed {
+    fn read_frame<R: BufRead>(&self, reader: &mut R) -> io::Result<Option<Vec<u8>>> {
+        // Only a stream which ends between frames ends cleanly
+        if try!(reader.fill_buf()).is_empty() {
+            return Ok(None)
+        }
+
+        let mut prefix = [0; 4];
+        try!(reader.read_exact(&mut prefix));
+        let len = prefix.iter().fold(0, |len, &b| (len << 8) | b as usize);
+
+        if len > self.max_len {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "frame too long"))
+        }
+
+        let mut frame = vec![0; len];
+        try!(reader.read_exact(&mut frame));
+
+        Ok(Some(frame))
+    }
+
+    fn write_frame<W: Write>(&self, writer: &mut W, frame: &[u8]) -> io::Result<()> {
+        if frame.len() > u32::max_value() as usize {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "frame too long"))
+        }
+
+        let len = frame.len() as u32;
+        let prefix = [(len >> 24) as u8, (len >> 16) as u8, (len >> 8) as u8, len as u8];
+
+        try!(writer.write_all(&prefix));
+        writer.write_all(frame)
+    }
+}
@@ -1,8 +1,14 @@
 use rand;
+use std::str;
+use std::io::BufRead;
+use std::net::{SocketAddr, IpAddr, Ipv4Addr, Ipv6Addr};
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
 use std::sync::*;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::*;
 
-use super::super::Event;
+use super::super::{Event, ThrottleMode};
 
 pub trait NoOp: Send {
     fn send_no_change(&mut self) -> bool;
@@ -42,24 +48,135 @@ impl<A> RunInput for ReceiverInput<A> where
 
         loop {
             match rx.recv() {
-                Ok(ref a) => {
+                Ok(a) => {
                     info!("RUN: ReceiverInput received data, sending");
-                    for (i, mut no_op_tx) in txs.lock().unwrap().iter_mut().enumerate() {
-                        if i == idx {
-                            match tx.send(Event::Changed(a.clone())) {
-                                Err(_) => return,
-                                _ => {},
-                            }
-                        } else {
-                            if no_op_tx.send_no_change() { return }
-                        }
-                    }
+                    if !send_changed(&tx, idx, &txs, a) { return }
                 },
                 Err(e) => {
                     info!("RUN: ReceiverInput sending error {}, exiting", e);
-                    for no_op_tx in txs.lock().unwrap().iter() {
-                        no_op_tx.send_exit();
+                    send_exit(&txs);
+                    return
+                },
+            }
+        }
+    }
+}
+
+/// Determines what happens when an input's data source is exhausted
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EndPolicy {
+    /// Send `Exit` to every input, shutting down the topology
+    Exit,
+    /// Stop sending data from this input, other inputs are unaffected
+    Complete,
+}
+
+pub struct IterInput<I, A> {
+    iter: I,
+    policy: EndPolicy,
+    tx: SyncSender<Event<A>>,
+}
+
+impl<I, A> IterInput<I, A> {
+    pub fn new(iter: I, policy: EndPolicy, tx: SyncSender<Event<A>>) -> IterInput<I, A> {
+        IterInput {
+            iter: iter,
+            policy: policy,
+            tx: tx,
+        }
+    }
+}
+
+impl<I, A> RunInput for IterInput<I, A> where
+    I: 'static + Send + Iterator<Item=A>,
+    A: 'static + Send + Clone,
+{
+    fn boxed_no_op(&self) -> Box<NoOp> {
+        Box::new(self.tx.clone())
+    }
+
+    fn run(self: Box<Self>, idx: usize, txs: Arc<Mutex<Vec<Box<NoOp>>>>) {
+        debug!("SETUP: running IterInput");
+        let inner = *self;
+        let IterInput {iter, policy, tx} = inner;
+
+        for a in iter {
+            info!("RUN: IterInput received data, sending");
+            if !send_changed(&tx, idx, &txs, a) { return }
+        }
+
+        match policy {
+            EndPolicy::Exit => {
+                info!("RUN: IterInput exhausted, exiting");
+                send_exit(&txs);
+            },
+            EndPolicy::Complete => {
+                info!("RUN: IterInput exhausted, completing");
+            },
+        }
+    }
+}
+
+pub struct LinesInput<R, F, A> {
+    reader: R,
+    parse: F,
+    tx: SyncSender<Event<A>>,
+}
+
+impl<R, F, A> LinesInput<R, F, A> {
+    pub fn new(reader: R, parse: F, tx: SyncSender<Event<A>>) -> LinesInput<R, F, A> {
+        LinesInput {
+            reader: reader,
+            parse: parse,
+            tx: tx,
+        }
+    }
+}
+
+impl<R, F, A> RunInput for LinesInput<R, F, A> where
+    R: 'static + Send + BufRead,
+    F: 'static + Send + Fn(&str) -> Option<A>,
+    A: 'static + Send + Clone,
+{
+    fn boxed_no_op(&self) -> Box<NoOp> {
+        Box::new(self.tx.clone())
+    }
+
+    fn run(self: Box<Self>, idx: usize, txs: Arc<Mutex<Vec<Box<NoOp>>>>) {
+        debug!("SETUP: running LinesInput");
+        let inner = *self;
+        let LinesInput {mut reader, parse, tx} = inner;
+
+        let mut line = Vec::new();
+
+        loop {
+            line.clear();
+
+            match reader.read_until(b'\n', &mut line) {
+                Ok(0) => {
+                    info!("RUN: LinesInput reached end of input, exiting");
+                    send_exit(&txs);
+                    return
+                },
+                Ok(_) => {
+                    if line.last() == Some(&b'\n') {
+                        line.pop();
                     }
+
+                    match str::from_utf8(&line).ok().and_then(|l| parse(l)) {
+                        Some(a) => {
+                            info!("RUN: LinesInput received data, sending");
+                            if !send_changed(&tx, idx, &txs, a) { return }
+                        },
+                        None => {
+                            info!("RUN: LinesInput failed to parse line, ignoring");
+                        },
+                    }
+                },
+                Err(e) => {
+                    info!("RUN: LinesInput sending error {}, exiting", e);
+                    send_exit(&txs);
                     return
                 },
             }
@@ -67,6 +184,59 @@ impl<A> RunInput for ReceiverInput<A> where
     }
 }
 
+// Sends `a` to the input at `idx` and no-change messages to all other inputs,
+// keeping every input in sync.  Returns `false` if the topology is shutting
+// down
+//
+pub fn send_changed<A>(tx: &SyncSender<Event<A>>, idx: usize, txs: &Arc<Mutex<Vec<Box<NoOp>>>>, a: A) -> bool where
+    A: 'static + Send,
+{
+    send_with(idx, txs, move || tx.send(Event::Changed(a)).is_ok())
+}
+
+// Like `send_changed`, but calls `f` in place of sending to the input at
+// `idx`, for inputs which send to more than one channel.  `f` should return
+// `false` if the topology is shutting down
+//
+pub fn send_with<F>(idx: usize, txs: &Arc<Mutex<Vec<Box<NoOp>>>>, f: F) -> bool where
+    F: FnOnce() -> bool,
+{
+    let mut f = Some(f);
+
+    for (i, no_op_tx) in txs.lock().unwrap().iter_mut().enumerate() {
+        if i == idx {
+            if !(f.take().unwrap())() { return false }
+        } else {
+            if no_op_tx.send_no_change() { return false }
+        }
+    }
+
+    true
+}
+
+// Returns an address which can be used to reach a socket bound to `addr`,
+// since sockets bound to an unspecified address can't be connected to
+//
+pub fn connectable_addr(addr: SocketAddr) -> SocketAddr {
+    let mut addr = addr;
+
+    match addr.ip() {
+        IpAddr::V4(ip) if ip.is_unspecified() => addr.set_ip(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1))),
+        IpAddr::V6(ip) if ip.is_unspecified() => addr.set_ip(IpAddr::V6(Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 1))),
+        _ => {},
+    }
+
+    addr
+}
+
+// Sends `Exit` to all inputs
+//
+pub fn send_exit(txs: &Arc<Mutex<Vec<Box<NoOp>>>>) {
+    for no_op_tx in txs.lock().unwrap().iter() {
+        no_op_tx.send_exit();
+    }
+}
+
 
 impl<A> NoOp for SyncSender<Event<A>> where
 A: Send
@@ -185,3 +355,245 @@ A: Send + Clone + rand::Rand,
         }
     }
 }
+
+pub struct DelayInput<A> {
+    rx: Receiver<(Instant, A)>,
+    delay: Duration,
+    tx: SyncSender<Event<A>>,
+    stopped: Arc<AtomicBool>,
+}
+
+impl<A> DelayInput<A> {
+    pub fn new(rx: Receiver<(Instant, A)>, delay: Duration, tx: SyncSender<Event<A>>) -> DelayInput<A> {
+        DelayInput {
+            rx: rx,
+            delay: delay,
+            tx: tx,
+            stopped: Arc::new(AtomicBool::new(false)),
+        }
+    }
+}
+
+impl<A> RunInput for DelayInput<A> where
+    A: 'static + Send + Clone,
+{
+    fn boxed_no_op(&self) -> Box<NoOp> {
+        Box::new(TimerNoOp { tx: self.tx.clone(), stopped: self.stopped.clone() })
+    }
+
+    fn run(self: Box<Self>, idx: usize, txs: Arc<Mutex<Vec<Box<NoOp>>>>) {
+        debug!("SETUP: running DelayInput");
+        let inner = *self;
+        let DelayInput {rx, delay, tx, stopped} = inner;
+
+        // Values are released in arrival order; since the delay is constant
+        // the front of the queue is always the next value due
+        let mut pending = VecDeque::new();
+
+        loop {
+            if stopped.load(Ordering::SeqCst) {
+                info!("RUN: DelayInput stopped, dropping {} pending values", pending.len());
+                return
+            }
+
+            let due = pending.front().map(|&(arrived, _)| arrived + delay);
+
+            match due {
+                Some(due) if due <= Instant::now() => {
+                    let (_, a) = pending.pop_front().unwrap();
+                    info!("RUN: DelayInput releasing delayed data, sending");
+                    if !send_changed(&tx, idx, &txs, a) { return }
+                    continue
+                },
+                _ => {},
+            }
+
+            let received = match due {
+                Some(due) => rx.recv_timeout(due - Instant::now()),
+                None => rx.recv().map_err(|_| RecvTimeoutError::Disconnected),
+            };
+
+            match received {
+                Ok(a) => pending.push_back(a),
+                Err(RecvTimeoutError::Timeout) => {},
+                // Upstream only closes once the topology has exited, by which
+                // point we've been stopped
+                Err(RecvTimeoutError::Disconnected) => return,
+            }
+        }
+    }
+}
+
+pub struct DebounceInput<A> {
+    rx: Receiver<(Instant, A)>,
+    quiet: Duration,
+    tx: SyncSender<Event<A>>,
+    stopped: Arc<AtomicBool>,
+}
+
+impl<A> DebounceInput<A> {
+    pub fn new(rx: Receiver<(Instant, A)>, quiet: Duration, tx: SyncSender<Event<A>>) -> DebounceInput<A> {
+        DebounceInput {
+            rx: rx,
+            quiet: quiet,
+            tx: tx,
+            stopped: Arc::new(AtomicBool::new(false)),
+        }
+    }
+}
+
+impl<A> RunInput for DebounceInput<A> where
+    A: 'static + Send + Clone,
+{
+    fn boxed_no_op(&self) -> Box<NoOp> {
+        Box::new(TimerNoOp { tx: self.tx.clone(), stopped: self.stopped.clone() })
+    }
+
+    fn run(self: Box<Self>, idx: usize, txs: Arc<Mutex<Vec<Box<NoOp>>>>) {
+        debug!("SETUP: running DebounceInput");
+        let inner = *self;
+        let DebounceInput {rx, quiet, tx, stopped} = inner;
+
+        // Each value received replaces the pending one and restarts the wait
+        let mut pending: Option<(Instant, A)> = None;
+
+        loop {
+            let received = match pending {
+                Some((arrived, _)) => {
+                    let (now, due) = (Instant::now(), arrived + quiet);
+                    if now >= due {
+                        Err(RecvTimeoutError::Timeout)
+                    } else {
+                        rx.recv_timeout(due - now)
+                    }
+                },
+                None => rx.recv().map_err(|_| RecvTimeoutError::Disconnected),
+            };
+
+            if stopped.load(Ordering::SeqCst) {
+                info!("RUN: DebounceInput stopped, exiting");
+                return
+            }
+
+            match received {
+                Ok(a) => pending = Some(a),
+                Err(RecvTimeoutError::Timeout) => {
+                    info!("RUN: DebounceInput input quiet, sending");
+                    let (_, a) = pending.take().unwrap();
+                    if !send_changed(&tx, idx, &txs, a) { return }
+                },
+                // Upstream only closes once the topology has exited
+                Err(RecvTimeoutError::Disconnected) => return,
+            }
+        }
+    }
+}
+
+pub struct ThrottleInput<A> {
+    rx: Receiver<(Instant, A)>,
+    interval: Duration,
+    mode: ThrottleMode,
+    tx: SyncSender<Event<A>>,
+    stopped: Arc<AtomicBool>,
+}
+
+impl<A> ThrottleInput<A> {
+    pub fn new(rx: Receiver<(Instant, A)>, interval: Duration, mode: ThrottleMode, tx: SyncSender<Event<A>>) -> ThrottleInput<A> {
+        ThrottleInput {
+            rx: rx,
+            interval: interval,
+            mode: mode,
+            tx: tx,
+            stopped: Arc::new(AtomicBool::new(false)),
+        }
+    }
+}
+
+impl<A> RunInput for ThrottleInput<A> where
+    A: 'static + Send + Clone,
+{
+    fn boxed_no_op(&self) -> Box<NoOp> {
+        Box::new(TimerNoOp { tx: self.tx.clone(), stopped: self.stopped.clone() })
+    }
+
+    fn run(self: Box<Self>, idx: usize, txs: Arc<Mutex<Vec<Box<NoOp>>>>) {
+        debug!("SETUP: running ThrottleInput");
+        let inner = *self;
+        let ThrottleInput {rx, interval, mode, tx, stopped} = inner;
+
+        // Each emission starts a new interval, the input is idle once an
+        // interval passes without any changes
+        let mut deadline: Option<Instant> = None;
+        let mut pending = None;
+
+        loop {
+            let received = match deadline {
+                Some(deadline) => {
+                    let now = Instant::now();
+                    if now >= deadline {
+                        Err(RecvTimeoutError::Timeout)
+                    } else {
+                        rx.recv_timeout(deadline - now)
+                    }
+                },
+                None => rx.recv().map_err(|_| RecvTimeoutError::Disconnected),
+            };
+
+            if stopped.load(Ordering::SeqCst) {
+                info!("RUN: ThrottleInput stopped, exiting");
+                return
+            }
+
+            match received {
+                Ok((arrived, a)) => {
+                    match (deadline, mode) {
+                        (None, ThrottleMode::Trailing) => {
+                            deadline = Some(arrived + interval);
+                            pending = Some(a);
+                        },
+                        (None, _) => {
+                            deadline = Some(arrived + interval);
+                            info!("RUN: ThrottleInput sending leading value");
+                            if !send_changed(&tx, idx, &txs, a) { return }
+                        },
+                        (Some(_), ThrottleMode::Leading) => {},
+                        (Some(_), _) => pending = Some(a),
+                    }
+                },
+                Err(RecvTimeoutError::Timeout) => {
+                    match pending.take() {
+                        Some(a) => {
+                            deadline = Some(Instant::now() + interval);
+                            info!("RUN: ThrottleInput sending trailing value");
+                            if !send_changed(&tx, idx, &txs, a) { return }
+                        },
+                        None => deadline = None,
+                    }
+                },
+                // Upstream only closes once the topology has exited
+                Err(RecvTimeoutError::Disconnected) => return,
+            }
+        }
+    }
+}
+
+// Shared by the inputs which hold values until a timer fires.  Stopping
+// makes sure values which haven't been released yet are dropped.
+//
+struct TimerNoOp<A> {
+    tx: SyncSender<Event<A>>,
+    stopped: Arc<AtomicBool>,
+}
+
+impl<A> NoOp for TimerNoOp<A> where
+    A: Send
+{
+    fn send_no_change(&mut self) -> bool {
+        self.tx.send_no_change()
+    }
+
+    fn send_exit(&self) {
+        self.stopped.store(true, Ordering::SeqCst);
+        self.tx.send_exit()
+    }
+}
@@ -0,0 +1,110 @@
+use super::super::{Event, Signal, SignalExt, SignalType, Push, Config};
+
+/// The result of a `keep_if` or `drop_if` operation
+///
+pub struct KeepIfSignal<F, A> where
+    F: 'static + Send + Fn(&A) -> bool,
+    A: 'static + Send + Clone,
+{
+    config: Config,
+    parent: Box<Signal<A>>,
+    f: F,
+    initial: SignalType<A>,
+}
+
+impl<F, A> KeepIfSignal<F, A> where
+    F: 'static + Send + Fn(&A) -> bool,
+    A: 'static + Send + Clone,
+{
+    pub fn new(config: Config, parent: Box<Signal<A>>, f: F, default: A) -> Self {
+        let initial = match parent.initial() {
+            SignalType::Constant(a) => {
+                if f(&a) { SignalType::Constant(a) } else { SignalType::Constant(default) }
+            },
+            SignalType::Dynamic(a) => {
+                if f(&a) { SignalType::Dynamic(a) } else { SignalType::Dynamic(default) }
+            },
+        };
+
+        KeepIfSignal {
+            config: config,
+            parent: parent,
+            f: f,
+            initial: initial,
+        }
+    }
+}
+
+impl<F, A> Signal<A> for KeepIfSignal<F, A> where
+    F: 'static + Send + Fn(&A) -> bool,
+    A: 'static + Send + Clone,
+{
+    fn config(&self) -> Config {
+        self.config.clone()
+    }
+
+    fn initial(&self) -> SignalType<A> {
+        self.initial.clone()
+    }
+
+    fn push_to(self: Box<Self>, target: Option<Box<Push<A>>>) {
+        let inner = *self;
+        let KeepIfSignal { config: _, parent, f, initial: _ } = inner;
+
+        debug!("SETUP: KeepIf pushing to parent");
+        parent.push_to(
+            Some(
+                Box::new(
+                    KeepIfPusher {
+                        child: target,
+                        f: f,
+                    }
+                )
+            )
+        );
+    }
+}
+impl<F, A> SignalExt<A> for KeepIfSignal<F, A> where
+    F: 'static + Send + Fn(&A) -> bool,
+    A: 'static + Send + Clone,
+{}
+
+struct KeepIfPusher<F, A> where
+    F: 'static + Send + Fn(&A) -> bool,
+    A: 'static + Send + Clone,
+{
+    child: Option<Box<Push<A>>>,
+    f: F,
+}
+
+impl<F, A> Push<A> for KeepIfPusher<F, A> where
+    F: 'static + Send + Fn(&A) -> bool,
+    A: 'static + Send + Clone,
+{
+    fn push(&mut self, event: Event<A>) {
+        let out = match event {
+            Event::Changed(a) => {
+                if (self.f)(&a) {
+                    info!("RUN: KeepIfPusher keeping Event::Changed");
+                    Event::Changed(a)
+                } else {
+                    info!("RUN: KeepIfPusher dropping Event::Changed");
+                    Event::Unchanged
+                }
+            },
+            Event::Unchanged => {
+                info!("RUN: KeepIfPusher handling Event::Unchanged");
+                Event::Unchanged
+            },
+            Event::Exit => {
+                info!("RUN: KeepIfPusher handling Event::Exit");
+                Event::Exit
+            },
+        };
+
+        match self.child {
+            Some(ref mut t) => t.push(out),
+            None => {},
+        }
+    }
+}
@@ -168,10 +168,18 @@ impl<F, A, B, C> SignalExt<C> for Lift2Signal<F, A, B, C> where
 {}
 
 // Passed up the 'push_to' chain, finalizes by sending to a channel
-struct InputPusher<A> {
+pub struct InputPusher<A> {
     tx: SyncSender<Event<A>>,
 }
 
+impl<A> InputPusher<A> {
+    pub fn new(tx: SyncSender<Event<A>>) -> InputPusher<A> {
+        InputPusher {
+            tx: tx,
+        }
+    }
+}
+
 impl<A> Push<A> for InputPusher<A> where
     A: 'static + Send,
 {
@@ -0,0 +1,134 @@
+use std::thread;
+use std::sync::mpsc::*;
+
+use super::super::{Value, Event, Signal, SignalExt, SignalType, Push, Config};
+use super::lift2::InputPusher;
+
+/// The result of combining a collection of same-typed signals
+///
+/// Works like `Lift2Signal`, but for any number of parents.  Each tick reads
+/// one event from every parent (in order) and calls `f` with a `Value` for
+/// each of them, as long as at least one parent changed.
+///
+pub struct LiftAllSignal<F, A, B> where
+    F: 'static + Send + Fn(Vec<Value<A>>) -> B,
+    A: 'static + Send + Clone,
+    B: 'static + Send + Clone,
+{
+    config: Config,
+    parents: Vec<Box<Signal<A>>>,
+    f: F,
+    initial: SignalType<B>,
+}
+
+impl<F, A, B> LiftAllSignal<F, A, B> where
+    F: 'static + Send + Fn(Vec<Value<A>>) -> B,
+    A: 'static + Send + Clone,
+    B: 'static + Send + Clone,
+{
+    pub fn new(config: Config, parents: Vec<Box<Signal<A>>>, f: F) -> Self {
+        let mut all_constant = true;
+        let values = parents.iter().map(|parent| {
+            match parent.initial() {
+                SignalType::Constant(a) => Value::Changed(a),
+                SignalType::Dynamic(a) => {
+                    all_constant = false;
+                    Value::Changed(a)
+                },
+            }
+        }).collect();
+
+        let b = f(values);
+        let initial = if all_constant {
+            SignalType::Constant(b)
+        } else {
+            SignalType::Dynamic(b)
+        };
+
+        LiftAllSignal {
+            config: config,
+            parents: parents,
+            f: f,
+            initial: initial,
+        }
+    }
+}
+
+impl<F, A, B> Signal<B> for LiftAllSignal<F, A, B> where
+    F: 'static + Send + Fn(Vec<Value<A>>) -> B,
+    A: 'static + Send + Clone,
+    B: 'static + Send + Clone,
+{
+    fn config(&self) -> Config {
+        self.config.clone()
+    }
+
+    fn initial(&self) -> SignalType<B> {
+        self.initial.clone()
+    }
+
+    fn push_to(self: Box<Self>, mut target: Option<Box<Push<B>>>) {
+        let inner = *self;
+        let LiftAllSignal {config, parents, f, initial: _} = inner;
+
+        let mut inputs = parents.into_iter().map(|parent| {
+            match parent.initial() {
+                SignalType::Constant(a) => (SignalType::Constant(a), None),
+                SignalType::Dynamic(a) => {
+                    let (tx, rx) = sync_channel(config.buffer_size.clone());
+                    thread::spawn(move || {
+                        parent.push_to(Some(Box::new(InputPusher::new(tx))));
+                    });
+
+                    (SignalType::Dynamic(a), Some(rx))
+                },
+            }
+        }).collect::<Vec<(SignalType<A>, Option<Receiver<Event<A>>>)>>();
+
+        loop {
+            let mut any_changed = false;
+            let mut values = Vec::with_capacity(inputs.len());
+
+            for &mut (ref mut last, ref rx) in inputs.iter_mut() {
+                let v = match (last, rx) {
+                    (&mut SignalType::Constant(ref a), _) => Value::Unchanged(a.clone()),
+                    (&mut SignalType::Dynamic(ref mut last), &Some(ref rx)) => {
+                        match rx.recv() {
+                            Ok(Event::Changed(a)) => {
+                                info!("RUN: LiftAll using changed value");
+                                any_changed = true;
+                                *last = a.clone();
+                                Value::Changed(a)
+                            },
+                            Ok(Event::Unchanged) => {
+                                info!("RUN: LiftAll using cached value");
+                                Value::Unchanged(last.clone())
+                            },
+                            Ok(Event::Exit) => return,
+                            Err(_) => return,
+                        }
+                    },
+                    (&mut SignalType::Dynamic(_), &None) => unreachable!(),
+                };
+
+                values.push(v);
+            }
+
+            let b = if any_changed {
+                Event::Changed(f(values))
+            } else {
+                Event::Unchanged
+            };
+
+            match target {
+                Some(ref mut t) => t.push(b),
+                None => {},
+            }
+        }
+    }
+}
+impl<F, A, B> SignalExt<B> for LiftAllSignal<F, A, B> where
+    F: 'static + Send + Fn(Vec<Value<A>>) -> B,
+    A: 'static + Send + Clone,
+    B: 'static + Send + Clone,
+{}
@@ -0,0 +1,191 @@
+use std::thread;
+use std::sync::mpsc::*;
+
+use super::super::{Value, Event, Signal, SignalExt, SignalType, Push, Config};
+use super::lift2::InputPusher;
+
+// Each `LiftNSignal` behaves exactly like `Lift2Signal`, just with more
+// parents.  Every dynamic parent runs in its own thread and pushes into a
+// channel, and each tick reads one event from each channel (in argument
+// order) before calling `f`.
+//
+// The macro takes the name of the signal, then for each parent its type
+// parameter, field name, a name for its event receiver and a name for its
+// cached value.  The final type parameter is the output type.
+//
+macro_rules! lift_n {
+    ($name:ident, $( ($t:ident, $parent:ident, $rx:ident, $last:ident) ),+ ; $out:ident) => {
+        /// The result of a `liftN` operation
+        ///
+        pub struct $name<F, $($t,)+ $out> where
+            F: 'static + Send + Fn($(Value<$t>),+) -> $out,
+            $($t: 'static + Send + Clone,)+
+            $out: 'static + Send + Clone,
+        {
+            config: Config,
+            $($parent: Box<Signal<$t>>,)+
+            f: F,
+            initial: SignalType<$out>,
+        }
+
+        impl<F, $($t,)+ $out> $name<F, $($t,)+ $out> where
+            F: 'static + Send + Fn($(Value<$t>),+) -> $out,
+            $($t: 'static + Send + Clone,)+
+            $out: 'static + Send + Clone,
+        {
+            pub fn new(config: Config, $($parent: Box<Signal<$t>>,)+ f: F) -> Self {
+                let mut all_constant = true;
+                $(
+                    let $last = match $parent.initial() {
+                        SignalType::Constant(v) => v,
+                        SignalType::Dynamic(v) => {
+                            all_constant = false;
+                            v
+                        },
+                    };
+                )+
+
+                let v = f($(Value::Changed($last)),+);
+                let initial = if all_constant {
+                    SignalType::Constant(v)
+                } else {
+                    SignalType::Dynamic(v)
+                };
+
+                $name {
+                    config: config,
+                    $($parent: $parent,)+
+                    f: f,
+                    initial: initial,
+                }
+            }
+        }
+
+        impl<F, $($t,)+ $out> Signal<$out> for $name<F, $($t,)+ $out> where
+            F: 'static + Send + Fn($(Value<$t>),+) -> $out,
+            $($t: 'static + Send + Clone,)+
+            $out: 'static + Send + Clone,
+        {
+            fn config(&self) -> Config {
+                self.config.clone()
+            }
+
+            fn initial(&self) -> SignalType<$out> {
+                self.initial.clone()
+            }
+
+            fn push_to(self: Box<Self>, mut target: Option<Box<Push<$out>>>) {
+                let inner = *self;
+                let $name {config, $($parent,)+ f, initial: _} = inner;
+
+                $(
+                    let (mut $last, $rx) = match $parent.initial() {
+                        SignalType::Constant(v) => (SignalType::Constant(v), None),
+                        SignalType::Dynamic(v) => {
+                            let (tx, rx) = sync_channel(config.buffer_size.clone());
+                            thread::spawn(move || {
+                                $parent.push_to(Some(Box::new(InputPusher::new(tx))));
+                            });
+
+                            (SignalType::Dynamic(v), Some(rx))
+                        },
+                    };
+                )+
+
+                loop {
+                    let mut any_changed = false;
+
+                    $(
+                        let $parent = match (&mut $last, &$rx) {
+                            (&mut SignalType::Constant(ref v), _) => Value::Unchanged(v.clone()),
+                            (&mut SignalType::Dynamic(ref mut last), &Some(ref rx)) => {
+                                match rx.recv() {
+                                    Ok(Event::Changed(v)) => {
+                                        info!("RUN: {} using changed value", stringify!($name));
+                                        any_changed = true;
+                                        *last = v.clone();
+                                        Value::Changed(v)
+                                    },
+                                    Ok(Event::Unchanged) => {
+                                        info!("RUN: {} using cached value", stringify!($name));
+                                        Value::Unchanged(last.clone())
+                                    },
+                                    Ok(Event::Exit) => return,
+                                    Err(_) => return,
+                                }
+                            },
+                            (&mut SignalType::Dynamic(_), &None) => unreachable!(),
+                        };
+                    )+
+
+                    let out = if any_changed {
+                        Event::Changed(f($($parent),+))
+                    } else {
+                        Event::Unchanged
+                    };
+
+                    match target {
+                        Some(ref mut t) => t.push(out),
+                        None => {},
+                    }
+                }
+            }
+        }
+        impl<F, $($t,)+ $out> SignalExt<$out> for $name<F, $($t,)+ $out> where
+            F: 'static + Send + Fn($(Value<$t>),+) -> $out,
+            $($t: 'static + Send + Clone,)+
+            $out: 'static + Send + Clone,
+        {}
+    }
+}
+
+lift_n!(Lift3Signal,
+    (A, a, a_rx, last_a),
+    (B, b, b_rx, last_b),
+    (C, c, c_rx, last_c)
+    ; R);
+
+lift_n!(Lift4Signal,
+    (A, a, a_rx, last_a),
+    (B, b, b_rx, last_b),
+    (C, c, c_rx, last_c),
+    (D, d, d_rx, last_d)
+    ; R);
+
+lift_n!(Lift5Signal,
+    (A, a, a_rx, last_a),
+    (B, b, b_rx, last_b),
+    (C, c, c_rx, last_c),
+    (D, d, d_rx, last_d),
+    (E, e, e_rx, last_e)
+    ; R);
+
+lift_n!(Lift6Signal,
+    (A, a, a_rx, last_a),
+    (B, b, b_rx, last_b),
+    (C, c, c_rx, last_c),
+    (D, d, d_rx, last_d),
+    (E, e, e_rx, last_e),
+    (G, g, g_rx, last_g)
+    ; R);
+
+lift_n!(Lift7Signal,
+    (A, a, a_rx, last_a),
+    (B, b, b_rx, last_b),
+    (C, c, c_rx, last_c),
+    (D, d, d_rx, last_d),
+    (E, e, e_rx, last_e),
+    (G, g, g_rx, last_g),
+    (H, h, h_rx, last_h)
+    ; R);
+
+lift_n!(Lift8Signal,
+    (A, a, a_rx, last_a),
+    (B, b, b_rx, last_b),
+    (C, c, c_rx, last_c),
+    (D, d, d_rx, last_d),
+    (E, e, e_rx, last_e),
+    (G, g, g_rx, last_g),
+    (H, h, h_rx, last_h),
+    (I, i, i_rx, last_i)
+    ; R);
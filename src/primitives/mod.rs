@@ -2,9 +2,28 @@
 //!
 pub mod async;
 pub mod channel;
+pub mod delay;
+pub mod drop_repeats;
+pub mod file;
+pub mod filter_map;
 pub mod fold;
+pub mod fold_n;
 pub mod fork;
+pub mod framing;
 pub mod input;
+pub mod keep_if;
 pub mod lift;
+pub mod lift_all;
 pub mod lift2;
+pub mod liftn;
+pub mod partition;
+pub mod previous;
+pub mod sample_on;
+pub mod tcp;
+pub mod try_lift;
+pub mod udp;
+#[cfg(unix)]
+pub mod unix;
+pub mod unzip;
 pub mod value;
+pub mod window;
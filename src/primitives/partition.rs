@@ -0,0 +1,105 @@
+use std::sync::*;
+use std::sync::mpsc::*;
+
+use super::super::{Event, Signal, SignalType, Push, Run};
+
+// A Partition is created internally when Builder#partition is called.  Like
+// Fork, it distributes incoming data to child Branch instances, but each
+// value is only sent as a change to one of two sets of branches.  The other
+// set receives `Unchanged`, so both stay synchronized with the topology.
+//
+pub struct Partition<F, A> where
+    F: 'static + Send + Fn(&A) -> bool,
+    A: 'static + Send,
+{
+    parent: Box<Signal<A>>,
+    f: F,
+    matched_txs: Arc<Mutex<Vec<SyncSender<Event<A>>>>>,
+    unmatched_txs: Arc<Mutex<Vec<SyncSender<Event<A>>>>>,
+}
+
+impl<F, A> Partition<F, A> where
+    F: 'static + Send + Fn(&A) -> bool,
+    A: 'static + Clone + Send,
+{
+    pub fn new(parent: Box<Signal<A>>, f: F, matched_txs: Arc<Mutex<Vec<SyncSender<Event<A>>>>>, unmatched_txs: Arc<Mutex<Vec<SyncSender<Event<A>>>>>) -> Partition<F, A> {
+        Partition {
+            parent: parent,
+            f: f,
+            matched_txs: matched_txs,
+            unmatched_txs: unmatched_txs,
+        }
+    }
+}
+
+impl<F, A> Run for Partition<F, A> where
+    F: 'static + Send + Fn(&A) -> bool,
+    A: 'static + Clone + Send,
+{
+    fn run(self: Box<Self>) {
+        match self.parent.initial() {
+            SignalType::Constant(_) => return,
+            SignalType::Dynamic(_) => {
+                debug!("Partition::run");
+
+                let inner = *self;
+                let Partition { parent, f, matched_txs, unmatched_txs } = inner;
+
+                parent.push_to(
+                    Some(
+                        Box::new(
+                            PartitionPusher {
+                                f: f,
+                                matched_txs: matched_txs,
+                                unmatched_txs: unmatched_txs,
+                            }
+                        )
+                    )
+                )
+            }
+        }
+    }
+}
+
+struct PartitionPusher<F, A> {
+    f: F,
+    matched_txs: Arc<Mutex<Vec<SyncSender<Event<A>>>>>,
+    unmatched_txs: Arc<Mutex<Vec<SyncSender<Event<A>>>>>,
+}
+
+fn send_all<A>(txs: &Arc<Mutex<Vec<SyncSender<Event<A>>>>>, event: Event<A>) where
+    A: 'static + Clone + Send,
+{
+    for tx in txs.lock().unwrap().iter() {
+        match tx.send(event.clone()) {
+            // We can't really terminate a child process, so just ignore errors...
+            _ => {},
+        }
+    }
+}
+
+impl<F, A> Push<A> for PartitionPusher<F, A> where
+    F: 'static + Send + Fn(&A) -> bool,
+    A: 'static + Clone + Send,
+{
+    fn push(&mut self, event: Event<A>) {
+        match event {
+            Event::Changed(a) => {
+                if (self.f)(&a) {
+                    debug!("PartitionPusher handling matched Event::Changed");
+                    send_all(&self.matched_txs, Event::Changed(a));
+                    send_all(&self.unmatched_txs, Event::Unchanged);
+                } else {
+                    debug!("PartitionPusher handling unmatched Event::Changed");
+                    send_all(&self.matched_txs, Event::Unchanged);
+                    send_all(&self.unmatched_txs, Event::Changed(a));
+                }
+            },
+            event => {
+                debug!("PartitionPusher handling Event");
+                send_all(&self.matched_txs, event.clone());
+                send_all(&self.unmatched_txs, event);
+            },
+        }
+    }
+}
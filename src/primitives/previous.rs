@@ -0,0 +1,95 @@
+use std::mem;
+
+use super::super::{Event, Signal, SignalExt, SignalType, Push, Config};
+
+/// The result of a `previous` operation
+///
+pub struct PreviousSignal<A> where
+    A: 'static + Send + Clone,
+{
+    config: Config,
+    parent: Box<Signal<A>>,
+    initial: SignalType<A>,
+}
+
+impl<A> PreviousSignal<A> where
+    A: 'static + Send + Clone,
+{
+    pub fn new(config: Config, parent: Box<Signal<A>>, default: A) -> Self {
+        let initial = match parent.initial() {
+            SignalType::Constant(_) => SignalType::Constant(default),
+            SignalType::Dynamic(_) => SignalType::Dynamic(default),
+        };
+
+        PreviousSignal {
+            config: config,
+            parent: parent,
+            initial: initial,
+        }
+    }
+}
+
+impl<A> Signal<A> for PreviousSignal<A> where
+    A: 'static + Send + Clone,
+{
+    fn config(&self) -> Config {
+        self.config.clone()
+    }
+
+    fn initial(&self) -> SignalType<A> {
+        self.initial.clone()
+    }
+
+    fn push_to(self: Box<Self>, target: Option<Box<Push<A>>>) {
+        let inner = *self;
+        let PreviousSignal { config: _, parent, initial: _ } = inner;
+
+        let last = parent.initial().unwrap();
+
+        debug!("SETUP: Previous pushing to parent");
+        parent.push_to(
+            Some(
+                Box::new(
+                    PreviousPusher {
+                        child: target,
+                        last: last,
+                    }
+                )
+            )
+        );
+    }
+}
+impl<A> SignalExt<A> for PreviousSignal<A> where
+    A: 'static + Send + Clone,
+{}
+
+struct PreviousPusher<A> {
+    child: Option<Box<Push<A>>>,
+    last: A,
+}
+
+impl<A> Push<A> for PreviousPusher<A> where
+    A: 'static + Send + Clone,
+{
+    fn push(&mut self, event: Event<A>) {
+        let out = match event {
+            Event::Changed(a) => {
+                info!("RUN: PreviousPusher handling Event::Changed");
+                Event::Changed(mem::replace(&mut self.last, a))
+            },
+            Event::Unchanged => {
+                info!("RUN: PreviousPusher handling Event::Unchanged");
+                Event::Unchanged
+            },
+            Event::Exit => {
+                info!("RUN: PreviousPusher handling Event::Exit");
+                Event::Exit
+            },
+        };
+
+        match self.child {
+            Some(ref mut t) => t.push(out),
+            None => {},
+        }
+    }
+}
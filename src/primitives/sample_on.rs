@@ -0,0 +1,108 @@
+use std::marker::*;
+
+use super::super::{Value, Event, Signal, SignalExt, SignalType, Push, Config};
+
+/// The result of a `sample_on` operation
+///
+/// Wraps a zipped `(data, trigger)` signal and only reports a change when
+/// the trigger side of the pair changed.
+///
+pub struct SampleOnSignal<T, A> where
+    T: 'static + Send + Clone,
+    A: 'static + Send + Clone,
+{
+    config: Config,
+    parent: Box<Signal<(Value<A>, Value<T>)>>,
+    initial: SignalType<A>,
+}
+
+impl<T, A> SampleOnSignal<T, A> where
+    T: 'static + Send + Clone,
+    A: 'static + Send + Clone,
+{
+    pub fn new(config: Config, parent: Box<Signal<(Value<A>, Value<T>)>>, trigger: SignalType<T>) -> Self {
+        let (a, _) = parent.initial().unwrap();
+
+        // Output only changes when the trigger does
+        let initial = match trigger {
+            SignalType::Constant(_) => SignalType::Constant(a.into_inner()),
+            SignalType::Dynamic(_) => SignalType::Dynamic(a.into_inner()),
+        };
+
+        SampleOnSignal {
+            config: config,
+            parent: parent,
+            initial: initial,
+        }
+    }
+}
+
+impl<T, A> Signal<A> for SampleOnSignal<T, A> where
+    T: 'static + Send + Clone,
+    A: 'static + Send + Clone,
+{
+    fn config(&self) -> Config {
+        self.config.clone()
+    }
+
+    fn initial(&self) -> SignalType<A> {
+        self.initial.clone()
+    }
+
+    fn push_to(self: Box<Self>, target: Option<Box<Push<A>>>) {
+        let inner = *self;
+        let SampleOnSignal { config: _, parent, initial: _ } = inner;
+
+        debug!("SETUP: SampleOn pushing to parent");
+        parent.push_to(
+            Some(
+                Box::new(
+                    SampleOnPusher {
+                        child: target,
+                        marker: PhantomData,
+                    }
+                )
+            )
+        );
+    }
+}
+impl<T, A> SignalExt<A> for SampleOnSignal<T, A> where
+    T: 'static + Send + Clone,
+    A: 'static + Send + Clone,
+{}
+
+struct SampleOnPusher<T, A> {
+    child: Option<Box<Push<A>>>,
+    marker: PhantomData<T>,
+}
+
+impl<T, A> Push<(Value<A>, Value<T>)> for SampleOnPusher<T, A> where
+    T: 'static + Send + Clone,
+    A: 'static + Send + Clone,
+{
+    fn push(&mut self, event: Event<(Value<A>, Value<T>)>) {
+        let out = match event {
+            Event::Changed((a, Value::Changed(_))) => {
+                info!("RUN: SampleOnPusher handling trigger change");
+                Event::Changed(a.into_inner())
+            },
+            Event::Changed((_, Value::Unchanged(_))) => {
+                info!("RUN: SampleOnPusher handling data change, ignoring");
+                Event::Unchanged
+            },
+            Event::Unchanged => {
+                info!("RUN: SampleOnPusher handling Event::Unchanged");
+                Event::Unchanged
+            },
+            Event::Exit => {
+                info!("RUN: SampleOnPusher handling Event::Exit");
+                Event::Exit
+            },
+        };
+
+        match self.child {
+            Some(ref mut t) => t.push(out),
+            None => {},
+        }
+    }
+}
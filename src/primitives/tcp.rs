@@ -0,0 +1,168 @@
+use std::thread;
+use std::io::BufReader;
+use std::net::{TcpListener, TcpStream, SocketAddr};
+use std::sync::*;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::*;
+
+use super::super::Event;
+use super::framing::{Framing, Newline};
+use super::input::{RunInput, NoOp, send_changed, connectable_addr};
+
+/// Receives newline-delimited frames from TCP connections
+///
+/// Each connection is read on its own thread, frames from all connections are
+/// decoded and sent to the topology in the order they're received.  Errors on
+/// a connection, including frames longer than `MAX_FRAME_SIZE`, only close
+/// that connection; the topology keeps running until it's stopped.
+///
+pub struct TcpInput<F, A> {
+    listener: TcpListener,
+    addr: SocketAddr,
+    buffer_size: usize,
+    decoder: F,
+    tx: SyncSender<Event<A>>,
+    stopped: Arc<AtomicBool>,
+}
+
+impl<F, A> TcpInput<F, A> {
+    /// `addr` is the address `listener` is bound to, and `buffer_size` is
+    /// the number of lines which can be read ahead of the topology
+    ///
+    pub fn new(listener: TcpListener, addr: SocketAddr, buffer_size: usize, decoder: F, tx: SyncSender<Event<A>>) -> TcpInput<F, A> {
+        TcpInput {
+            listener: listener,
+            addr: addr,
+            buffer_size: buffer_size,
+            decoder: decoder,
+            tx: tx,
+            stopped: Arc::new(AtomicBool::new(false)),
+        }
+    }
+}
+
+impl<F, A> RunInput for TcpInput<F, A> where
+    F: 'static + Send + Fn(&str) -> Option<A>,
+    A: 'static + Send + Clone,
+{
+    fn boxed_no_op(&self) -> Box<NoOp> {
+        Box::new(TcpNoOp { tx: self.tx.clone(), addr: self.addr, stopped: self.stopped.clone() })
+    }
+
+    fn run(self: Box<Self>, idx: usize, txs: Arc<Mutex<Vec<Box<NoOp>>>>) {
+        debug!("SETUP: running TcpInput");
+        let inner = *self;
+        let TcpInput {listener, addr: _, buffer_size, decoder, tx, stopped} = inner;
+
+        // Bounded, so readers are blocked when the topology falls behind
+        let (line_tx, line_rx) = sync_channel(buffer_size);
+
+        let accept_stopped = stopped.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                // `TcpNoOp` connects once it's stopped to wake us up
+                if accept_stopped.load(Ordering::SeqCst) { return }
+
+                match stream {
+                    Ok(stream) => {
+                        let line_tx = line_tx.clone();
+                        thread::spawn(move || read_lines(stream, line_tx));
+                    },
+                    Err(e) => {
+                        info!("RUN: TcpInput failed to accept connection: {}", e);
+                    },
+                }
+            }
+        });
+
+        loop {
+            match line_rx.recv() {
+                Ok(line) => {
+                    // Connections may still be open after the topology stops
+                    if stopped.load(Ordering::SeqCst) {
+                        info!("RUN: TcpInput stopped, exiting");
+                        return
+                    }
+
+                    let line = match String::from_utf8(line) {
+                        Ok(line) => line,
+                        Err(_) => {
+                            info!("RUN: TcpInput read invalid UTF-8, ignoring");
+                            continue
+                        },
+                    };
+
+                    match decoder(&line) {
+                        Some(a) => {
+                            info!("RUN: TcpInput received data, sending");
+                            if !send_changed(&tx, idx, &txs, a) { return }
+                        },
+                        None => {
+                            info!("RUN: TcpInput failed to decode frame, ignoring");
+                        },
+                    }
+                },
+                Err(_) => {
+                    // Only happens once the listener has stopped accepting
+                    // connections, so the topology is already shutting down
+                    info!("RUN: TcpInput stopped, exiting");
+                    return
+                },
+            }
+        }
+    }
+}
+
+// Forwards each line read from `stream` until the connection is closed or the
+// input stops listening
+//
+fn read_lines(stream: TcpStream, line_tx: SyncSender<Vec<u8>>) {
+    let mut reader = BufReader::new(stream);
+    let framing = Newline::new();
+
+    loop {
+        match framing.read_frame(&mut reader) {
+            Ok(Some(mut line)) => {
+                if line.last() == Some(&b'\r') {
+                    line.pop();
+                }
+
+                match line_tx.send(line) {
+                    Err(_) => return,
+                    _ => {},
+                }
+            },
+            Ok(None) => return,
+            Err(e) => {
+                info!("RUN: TcpInput connection failed: {}, closing", e);
+                return
+            },
+        }
+    }
+}
+
+struct TcpNoOp<A> {
+    tx: SyncSender<Event<A>>,
+    addr: SocketAddr,
+    stopped: Arc<AtomicBool>,
+}
+
+impl<A> NoOp for TcpNoOp<A> where
+    A: Send
+{
+    fn send_no_change(&mut self) -> bool {
+        self.tx.send_no_change()
+    }
+
+    fn send_exit(&self) {
+        // Exit can be sent more than once, only wake the listener the first
+        // time
+        if !self.stopped.swap(true, Ordering::SeqCst) {
+            match TcpStream::connect(connectable_addr(self.addr)) {
+                _ => {},
+            }
+        }
+
+        self.tx.send_exit()
+    }
+}
@@ -0,0 +1,122 @@
+use std::sync::*;
+use std::sync::mpsc::*;
+
+use super::super::{Event, Signal, SignalType, Push, Run};
+
+// A TryLift is created internally when Builder#try_lift is called.  Like
+// Partition, it distributes incoming data to two sets of child Branch
+// instances: values which `f` transforms successfully are sent to the first,
+// and errors are sent to the second.  Whichever set doesn't receive a change
+// receives `Unchanged`, so both stay synchronized with the topology.
+//
+pub struct TryLift<F, A, B, E> where
+    F: 'static + Send + Fn(A) -> Result<B, E>,
+    A: 'static + Send,
+    B: 'static + Send,
+    E: 'static + Send,
+{
+    parent: Box<Signal<A>>,
+    f: F,
+    ok_txs: Arc<Mutex<Vec<SyncSender<Event<B>>>>>,
+    err_txs: Arc<Mutex<Vec<SyncSender<Event<E>>>>>,
+}
+
+impl<F, A, B, E> TryLift<F, A, B, E> where
+    F: 'static + Send + Fn(A) -> Result<B, E>,
+    A: 'static + Clone + Send,
+    B: 'static + Clone + Send,
+    E: 'static + Clone + Send,
+{
+    pub fn new(parent: Box<Signal<A>>, f: F, ok_txs: Arc<Mutex<Vec<SyncSender<Event<B>>>>>, err_txs: Arc<Mutex<Vec<SyncSender<Event<E>>>>>) -> TryLift<F, A, B, E> {
+        TryLift {
+            parent: parent,
+            f: f,
+            ok_txs: ok_txs,
+            err_txs: err_txs,
+        }
+    }
+}
+
+impl<F, A, B, E> Run for TryLift<F, A, B, E> where
+    F: 'static + Send + Fn(A) -> Result<B, E>,
+    A: 'static + Clone + Send,
+    B: 'static + Clone + Send,
+    E: 'static + Clone + Send,
+{
+    fn run(self: Box<Self>) {
+        match self.parent.initial() {
+            SignalType::Constant(_) => return,
+            SignalType::Dynamic(_) => {
+                debug!("TryLift::run");
+
+                let inner = *self;
+                let TryLift { parent, f, ok_txs, err_txs } = inner;
+
+                parent.push_to(
+                    Some(
+                        Box::new(
+                            TryLiftPusher {
+                                f: f,
+                                ok_txs: ok_txs,
+                                err_txs: err_txs,
+                            }
+                        )
+                    )
+                )
+            }
+        }
+    }
+}
+
+struct TryLiftPusher<F, B, E> {
+    f: F,
+    ok_txs: Arc<Mutex<Vec<SyncSender<Event<B>>>>>,
+    err_txs: Arc<Mutex<Vec<SyncSender<Event<E>>>>>,
+}
+
+fn send_all<A>(txs: &Arc<Mutex<Vec<SyncSender<Event<A>>>>>, event: Event<A>) where
+    A: 'static + Clone + Send,
+{
+    for tx in txs.lock().unwrap().iter() {
+        match tx.send(event.clone()) {
+            // We can't really terminate a child process, so just ignore errors...
+            _ => {},
+        }
+    }
+}
+
+impl<F, A, B, E> Push<A> for TryLiftPusher<F, B, E> where
+    F: 'static + Send + Fn(A) -> Result<B, E>,
+    A: 'static + Clone + Send,
+    B: 'static + Clone + Send,
+    E: 'static + Clone + Send,
+{
+    fn push(&mut self, event: Event<A>) {
+        match event {
+            Event::Changed(a) => {
+                match (self.f)(a) {
+                    Ok(b) => {
+                        debug!("TryLiftPusher handling Event::Changed");
+                        send_all(&self.ok_txs, Event::Changed(b));
+                        send_all(&self.err_txs, Event::Unchanged);
+                    },
+                    Err(e) => {
+                        debug!("TryLiftPusher handling Event::Changed with error");
+                        send_all(&self.ok_txs, Event::Unchanged);
+                        send_all(&self.err_txs, Event::Changed(e));
+                    },
+                }
+            },
+            Event::Unchanged => {
+                debug!("TryLiftPusher handling Event::Unchanged");
+                send_all(&self.ok_txs, Event::Unchanged);
+                send_all(&self.err_txs, Event::Unchanged);
+            },
+            Event::Exit => {
+                debug!("TryLiftPusher handling Event::Exit");
+                send_all(&self.ok_txs, Event::Exit);
+                send_all(&self.err_txs, Event::Exit);
+            },
+        }
+    }
+}
@@ -0,0 +1,235 @@
+use std::cmp;
+use std::thread;
+use std::net::{UdpSocket, SocketAddr};
+use std::time::Duration;
+use std::sync::*;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::*;
+
+use super::super::Event;
+use super::input::{RunInput, NoOp, send_with, connectable_addr};
+
+/// The largest payload a UDP datagram can carry
+///
+pub const MAX_DATAGRAM_SIZE: usize = 65507;
+
+/// Determines what happens to datagrams which can't be sent to the topology
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DatagramPolicy {
+    /// Discard the datagram
+    Drop,
+    /// Send a `DatagramError` to the input's error signal
+    Error,
+}
+
+/// Describes a datagram which couldn't be sent to the topology
+///
+#[derive(Clone, Debug, PartialEq)]
+pub enum DatagramError {
+    /// The datagram from the given address was larger than the maximum size
+    Oversized(SocketAddr),
+    /// The datagram from the given address couldn't be decoded
+    Undecodable(SocketAddr),
+}
+
+/// A snapshot of the number of datagrams a UDP input has rejected
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct DatagramCounts {
+    pub oversized: usize,
+    pub undecodable: usize,
+}
+
+/// Counts the datagrams a UDP input has rejected, regardless of its policy
+///
+/// Returned by `Builder::listen_udp_with`.  Clones share the same counts, so
+/// the handle can be moved out of the builder and read while the topology
+/// runs.
+///
+#[derive(Clone, Debug, Default)]
+pub struct DatagramCounters {
+    oversized: Arc<AtomicUsize>,
+    undecodable: Arc<AtomicUsize>,
+}
+
+impl DatagramCounters {
+    pub fn new() -> DatagramCounters {
+        Default::default()
+    }
+
+    /// Returns the number of datagrams rejected so far
+    ///
+    pub fn counts(&self) -> DatagramCounts {
+        DatagramCounts {
+            oversized: self.oversized.load(Ordering::SeqCst),
+            undecodable: self.undecodable.load(Ordering::SeqCst),
+        }
+    }
+
+    fn record(&self, err: &DatagramError) {
+        match *err {
+            DatagramError::Oversized(_) => self.oversized.fetch_add(1, Ordering::SeqCst),
+            DatagramError::Undecodable(_) => self.undecodable.fetch_add(1, Ordering::SeqCst),
+        };
+    }
+}
+
+/// Receives datagrams from a UDP socket
+///
+/// Decoded values are sent to `tx`.  Datagrams which are larger than
+/// `max_size` or can't be decoded are counted, and depending on the policy
+/// either discarded or sent to `err_tx`.
+///
+pub struct UdpInput<F, A> {
+    socket: UdpSocket,
+    addr: SocketAddr,
+    max_size: usize,
+    policy: DatagramPolicy,
+    decode: F,
+    tx: SyncSender<Event<A>>,
+    err_tx: SyncSender<Event<Option<DatagramError>>>,
+    counters: DatagramCounters,
+    stopped: Arc<AtomicBool>,
+}
+
+impl<F, A> UdpInput<F, A> {
+    /// `addr` is the address `socket` is bound to
+    ///
+    pub fn new(
+        socket: UdpSocket,
+        addr: SocketAddr,
+        max_size: usize,
+        policy: DatagramPolicy,
+        decode: F,
+        tx: SyncSender<Event<A>>,
+        err_tx: SyncSender<Event<Option<DatagramError>>>,
+        counters: DatagramCounters,
+    ) -> UdpInput<F, A> {
+        UdpInput {
+            socket: socket,
+            addr: addr,
+            max_size: max_size,
+            policy: policy,
+            decode: decode,
+            tx: tx,
+            err_tx: err_tx,
+            counters: counters,
+            stopped: Arc::new(AtomicBool::new(false)),
+        }
+    }
+}
+
+impl<F, A> RunInput for UdpInput<F, A> where
+    F: 'static + Send + Fn(&[u8], SocketAddr) -> Option<A>,
+    A: 'static + Send + Clone,
+{
+    fn boxed_no_op(&self) -> Box<NoOp> {
+        Box::new(UdpNoOp {
+            tx: self.tx.clone(),
+            err_tx: self.err_tx.clone(),
+            addr: self.addr,
+            stopped: self.stopped.clone(),
+        })
+    }
+
+    fn run(self: Box<Self>, idx: usize, txs: Arc<Mutex<Vec<Box<NoOp>>>>) {
+        debug!("SETUP: running UdpInput");
+        let inner = *self;
+        let UdpInput {socket, addr: _, max_size, policy, decode, tx, err_tx, counters, stopped} = inner;
+
+        // One extra byte lets us tell oversized datagrams from ones which
+        // exactly fill the buffer, since the OS truncates without telling us
+        let mut buf = vec![0; max_size + 1];
+        let mut backoff = Duration::from_millis(0);
+
+        loop {
+            let received = socket.recv_from(&mut buf);
+
+            // `UdpNoOp` sends a datagram once it's stopped to wake us up
+            if stopped.load(Ordering::SeqCst) {
+                info!("RUN: UdpInput stopped, exiting");
+                return
+            }
+
+            let (len, from) = match received {
+                Ok(received) => {
+                    backoff = Duration::from_millis(0);
+                    received
+                },
+                Err(e) => {
+                    // Don't spin if the socket keeps failing
+                    backoff = cmp::min(cmp::max(backoff * 2, Duration::from_millis(1)), Duration::from_secs(1));
+                    info!("RUN: UdpInput failed to receive datagram: {}, retrying in {:?}", e, backoff);
+                    thread::sleep(backoff);
+                    continue
+                },
+            };
+
+            let decoded = if len > max_size {
+                Err(DatagramError::Oversized(from))
+            } else {
+                decode(&buf[..len], from).ok_or(DatagramError::Undecodable(from))
+            };
+
+            let sent = match decoded {
+                Ok(a) => {
+                    info!("RUN: UdpInput received data, sending");
+                    send_with(idx, &txs, || {
+                        let sent = tx.send(Event::Changed(a)).is_ok();
+                        err_tx.send(Event::Unchanged).is_ok() && sent
+                    })
+                },
+                Err(e) => {
+                    counters.record(&e);
+
+                    match policy {
+                        DatagramPolicy::Drop => {
+                            info!("RUN: UdpInput rejected datagram {:?}, dropping", e);
+                            true
+                        },
+                        DatagramPolicy::Error => {
+                            info!("RUN: UdpInput rejected datagram {:?}, sending error", e);
+                            send_with(idx, &txs, || {
+                                let sent = tx.send(Event::Unchanged).is_ok();
+                                err_tx.send(Event::Changed(Some(e))).is_ok() && sent
+                            })
+                        },
+                    }
+                },
+            };
+
+            if !sent { return }
+        }
+    }
+}
+
+struct UdpNoOp<A> {
+    tx: SyncSender<Event<A>>,
+    err_tx: SyncSender<Event<Option<DatagramError>>>,
+    addr: SocketAddr,
+    stopped: Arc<AtomicBool>,
+}
+
+impl<A> NoOp for UdpNoOp<A> where
+    A: Send
+{
+    fn send_no_change(&mut self) -> bool {
+        // Evaluate both so the error signal stays in sync
+        let closed = self.tx.send_no_change();
+        self.err_tx.send_no_change() || closed
+    }
+
+    fn send_exit(&self) {
+        // Exit can be sent more than once, only wake the socket the first time
+        if !self.stopped.swap(true, Ordering::SeqCst) {
+            let local = if self.addr.is_ipv4() { "0.0.0.0:0" } else { "[::]:0" };
+            match UdpSocket::bind(local).and_then(|s| s.send_to(&[], connectable_addr(self.addr))) {
+                _ => {},
+            }
+        }
+
+        self.tx.send_exit();
+        self.err_tx.send_exit();
+    }
+}
@@ -0,0 +1,262 @@
+use std::fs;
+use std::thread;
+use std::io::{BufReader, BufWriter, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::PathBuf;
+use std::sync::*;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::*;
+
+use super::super::{Event, Signal, SignalType, Push, Run};
+use super::framing::Framing;
+use super::input::{RunInput, NoOp, send_changed};
+
+/// Receives frames from Unix domain socket connections
+///
+/// Like `TcpInput`, each connection is read on its own thread and errors on a
+/// connection only close that connection.  The socket file is removed when
+/// the topology is stopped.
+///
+pub struct UnixInput<Fr, F, A> {
+    listener: UnixListener,
+    path: PathBuf,
+    buffer_size: usize,
+    framing: Fr,
+    decode: F,
+    tx: SyncSender<Event<A>>,
+    stopped: Arc<AtomicBool>,
+}
+
+impl<Fr, F, A> UnixInput<Fr, F, A> {
+    /// `buffer_size` is the number of frames which can be read ahead of the
+    /// topology
+    ///
+    pub fn new(listener: UnixListener, path: PathBuf, buffer_size: usize, framing: Fr, decode: F, tx: SyncSender<Event<A>>) -> UnixInput<Fr, F, A> {
+        UnixInput {
+            listener: listener,
+            path: path,
+            buffer_size: buffer_size,
+            framing: framing,
+            decode: decode,
+            tx: tx,
+            stopped: Arc::new(AtomicBool::new(false)),
+        }
+    }
+}
+
+impl<Fr, F, A> RunInput for UnixInput<Fr, F, A> where
+    Fr: Framing,
+    F: 'static + Send + Fn(&[u8]) -> Option<A>,
+    A: 'static + Send + Clone,
+{
+    fn boxed_no_op(&self) -> Box<NoOp> {
+        Box::new(UnixNoOp { tx: self.tx.clone(), path: self.path.clone(), stopped: self.stopped.clone() })
+    }
+
+    fn run(self: Box<Self>, idx: usize, txs: Arc<Mutex<Vec<Box<NoOp>>>>) {
+        debug!("SETUP: running UnixInput");
+        let inner = *self;
+        let UnixInput {listener, path: _, buffer_size, framing, decode, tx, stopped} = inner;
+
+        // Bounded, so readers are blocked when the topology falls behind
+        let (frame_tx, frame_rx) = sync_channel(buffer_size);
+        let framing = Arc::new(framing);
+
+        let accept_stopped = stopped.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                // `UnixNoOp` connects once it's stopped to wake us up
+                if accept_stopped.load(Ordering::SeqCst) { return }
+
+                match stream {
+                    Ok(stream) => {
+                        let frame_tx = frame_tx.clone();
+                        let framing = framing.clone();
+                        thread::spawn(move || read_frames(stream, &*framing, frame_tx));
+                    },
+                    Err(e) => {
+                        info!("RUN: UnixInput failed to accept connection: {}", e);
+                    },
+                }
+            }
+        });
+
+        loop {
+            match frame_rx.recv() {
+                Ok(frame) => {
+                    // Connections may still be open once we've stopped
+                    if stopped.load(Ordering::SeqCst) {
+                        info!("RUN: UnixInput stopped, exiting");
+                        return
+                    }
+
+                    match decode(&frame) {
+                        Some(a) => {
+                            info!("RUN: UnixInput received data, sending");
+                            if !send_changed(&tx, idx, &txs, a) { return }
+                        },
+                        None => {
+                            info!("RUN: UnixInput failed to decode frame, ignoring");
+                        },
+                    }
+                },
+                Err(_) => {
+                    // Only happens once the listener has stopped accepting
+                    // connections, so the topology is already shutting down
+                    info!("RUN: UnixInput stopped, exiting");
+                    return
+                },
+            }
+        }
+    }
+}
+
+// Forwards each frame read from `stream` until the connection is closed or
+// the input stops listening
+//
+fn read_frames<Fr>(stream: UnixStream, framing: &Fr, frame_tx: SyncSender<Vec<u8>>) where
+    Fr: Framing,
+{
+    let mut reader = BufReader::new(stream);
+
+    loop {
+        match framing.read_frame(&mut reader) {
+            Ok(Some(frame)) => {
+                match frame_tx.send(frame) {
+                    Err(_) => return,
+                    _ => {},
+                }
+            },
+            Ok(None) => return,
+            Err(e) => {
+                info!("RUN: UnixInput connection failed: {}, closing", e);
+                return
+            },
+        }
+    }
+}
+
+struct UnixNoOp<A> {
+    tx: SyncSender<Event<A>>,
+    path: PathBuf,
+    stopped: Arc<AtomicBool>,
+}
+
+impl<A> NoOp for UnixNoOp<A> where
+    A: Send
+{
+    fn send_no_change(&mut self) -> bool {
+        self.tx.send_no_change()
+    }
+
+    fn send_exit(&self) {
+        // Exit can be sent more than once, only clean up the first time
+        if !self.stopped.swap(true, Ordering::SeqCst) {
+            match UnixStream::connect(&self.path) {
+                _ => {},
+            }
+            match fs::remove_file(&self.path) {
+                Err(e) => info!("RUN: UnixInput failed to remove socket file: {}", e),
+                _ => {},
+            }
+        }
+
+        self.tx.send_exit()
+    }
+}
+
+// A UnixSink is created internally when Builder#write_unix is called.  It
+// writes the initial value and each change of its parent to a Unix domain
+// socket.
+//
+pub struct UnixSink<Fr, F, A> where
+    A: 'static + Send,
+{
+    parent: Box<Signal<A>>,
+    stream: UnixStream,
+    framing: Fr,
+    encode: F,
+}
+
+impl<Fr, F, A> UnixSink<Fr, F, A> where
+    A: 'static + Send,
+{
+    pub fn new(parent: Box<Signal<A>>, stream: UnixStream, framing: Fr, encode: F) -> UnixSink<Fr, F, A> {
+        UnixSink {
+            parent: parent,
+            stream: stream,
+            framing: framing,
+            encode: encode,
+        }
+    }
+}
+
+impl<Fr, F, A> Run for UnixSink<Fr, F, A> where
+    Fr: Framing,
+    F: 'static + Send + Fn(&A) -> Vec<u8>,
+    A: 'static + Clone + Send,
+{
+    fn run(self: Box<Self>) {
+        debug!("UnixSink::run");
+
+        let inner = *self;
+        let UnixSink { parent, stream, framing, encode } = inner;
+
+        let mut pusher = UnixSinkPusher {
+            writer: BufWriter::new(stream),
+            framing: framing,
+            encode: encode,
+        };
+
+        match parent.initial() {
+            SignalType::Constant(a) => pusher.write(&a),
+            SignalType::Dynamic(a) => {
+                pusher.write(&a);
+                parent.push_to(Some(Box::new(pusher)));
+            },
+        }
+    }
+}
+
+struct UnixSinkPusher<Fr, F> {
+    writer: BufWriter<UnixStream>,
+    framing: Fr,
+    encode: F,
+}
+
+impl<Fr, F> UnixSinkPusher<Fr, F> where
+    Fr: Framing,
+{
+    fn write<A>(&mut self, a: &A) where
+        F: Fn(&A) -> Vec<u8>,
+    {
+        let frame = (self.encode)(a);
+
+        // Write errors shouldn't stop the topology, so just log them
+        match self.framing.write_frame(&mut self.writer, &frame).and_then(|_| self.writer.flush()) {
+            Err(e) => info!("RUN: UnixSink failed to write frame: {}", e),
+            _ => {},
+        }
+    }
+}
+
+impl<Fr, F, A> Push<A> for UnixSinkPusher<Fr, F> where
+    Fr: Framing,
+    F: 'static + Send + Fn(&A) -> Vec<u8>,
+    A: 'static + Clone + Send,
+{
+    fn push(&mut self, event: Event<A>) {
+        match event {
+            Event::Changed(a) => {
+                debug!("UnixSink handling Event Changed - writing frame");
+                self.write(&a);
+            },
+            Event::Unchanged => {
+                debug!("UnixSink handling Event Unchanged - doing nothing");
+            },
+            Event::Exit => {
+                debug!("UnixSink handling Event Exit");
+            },
+        }
+    }
+}
@@ -0,0 +1,128 @@
+use std::sync::*;
+use std::sync::mpsc::*;
+
+use super::super::{Event, Signal, SignalType, Push, Run};
+
+// An Unzip is created internally when Builder#unzip is called.  Like Fork, it
+// distributes incoming data to child Branch instances, but the components of
+// each pair are sent to separate sets of branches.  A component is only sent
+// as a change if `eq` reports that it differs from the component's previous
+// value.
+//
+pub struct Unzip<A, B> where
+    A: 'static + Send,
+    B: 'static + Send,
+{
+    parent: Box<Signal<(A, B)>>,
+    eq_a: fn(&A, &A) -> bool,
+    eq_b: fn(&B, &B) -> bool,
+    left_txs: Arc<Mutex<Vec<SyncSender<Event<A>>>>>,
+    right_txs: Arc<Mutex<Vec<SyncSender<Event<B>>>>>,
+}
+
+impl<A, B> Unzip<A, B> where
+    A: 'static + Clone + Send,
+    B: 'static + Clone + Send,
+{
+    pub fn new(
+        parent: Box<Signal<(A, B)>>,
+        eq_a: fn(&A, &A) -> bool,
+        eq_b: fn(&B, &B) -> bool,
+        left_txs: Arc<Mutex<Vec<SyncSender<Event<A>>>>>,
+        right_txs: Arc<Mutex<Vec<SyncSender<Event<B>>>>>,
+    ) -> Unzip<A, B> {
+        Unzip {
+            parent: parent,
+            eq_a: eq_a,
+            eq_b: eq_b,
+            left_txs: left_txs,
+            right_txs: right_txs,
+        }
+    }
+}
+
+impl<A, B> Run for Unzip<A, B> where
+    A: 'static + Clone + Send,
+    B: 'static + Clone + Send,
+{
+    fn run(self: Box<Self>) {
+        match self.parent.initial() {
+            SignalType::Constant(_) => return,
+            SignalType::Dynamic((a, b)) => {
+                debug!("Unzip::run");
+
+                let inner = *self;
+                let Unzip { parent, eq_a, eq_b, left_txs, right_txs } = inner;
+
+                parent.push_to(
+                    Some(
+                        Box::new(
+                            UnzipPusher {
+                                left: Component { eq: eq_a, last: a, txs: left_txs },
+                                right: Component { eq: eq_b, last: b, txs: right_txs },
+                            }
+                        )
+                    )
+                )
+            }
+        }
+    }
+}
+
+struct Component<A> {
+    eq: fn(&A, &A) -> bool,
+    last: A,
+    txs: Arc<Mutex<Vec<SyncSender<Event<A>>>>>,
+}
+
+impl<A> Component<A> where
+    A: 'static + Clone + Send,
+{
+    fn send(&self, event: Event<A>) {
+        for tx in self.txs.lock().unwrap().iter() {
+            match tx.send(event.clone()) {
+                // We can't really terminate a child process, so just ignore errors...
+                _ => {},
+            }
+        }
+    }
+
+    fn send_changed(&mut self, a: A) {
+        if (self.eq)(&a, &self.last) {
+            self.send(Event::Unchanged)
+        } else {
+            self.last = a.clone();
+            self.send(Event::Changed(a))
+        }
+    }
+}
+
+struct UnzipPusher<A, B> {
+    left: Component<A>,
+    right: Component<B>,
+}
+
+impl<A, B> Push<(A, B)> for UnzipPusher<A, B> where
+    A: 'static + Clone + Send,
+    B: 'static + Clone + Send,
+{
+    fn push(&mut self, event: Event<(A, B)>) {
+        match event {
+            Event::Changed((a, b)) => {
+                debug!("UnzipPusher handling Event::Changed");
+                self.left.send_changed(a);
+                self.right.send_changed(b);
+            },
+            Event::Unchanged => {
+                debug!("UnzipPusher handling Event::Unchanged");
+                self.left.send(Event::Unchanged);
+                self.right.send(Event::Unchanged);
+            },
+            Event::Exit => {
+                debug!("UnzipPusher handling Event::Exit");
+                self.left.send(Event::Exit);
+                self.right.send(Event::Exit);
+            },
+        }
+    }
+}
@@ -0,0 +1,220 @@
+use std::collections::VecDeque;
+use std::mem;
+
+use super::super::{Event, Signal, SignalExt, SignalType, Push, Config};
+
+/// The result of a `window` operation
+///
+/// The pusher updates its buffer in place, and only copies it into a new
+/// `VecDeque` when there's a downstream consumer to send it to.
+///
+pub struct WindowSignal<A> where
+    A: 'static + Send + Clone,
+{
+    config: Config,
+    parent: Box<Signal<A>>,
+    size: usize,
+    initial: SignalType<VecDeque<A>>,
+}
+
+impl<A> WindowSignal<A> where
+    A: 'static + Send + Clone,
+{
+    pub fn new(config: Config, parent: Box<Signal<A>>, size: usize) -> Self {
+        assert!(size > 0, "window size must be greater than zero");
+
+        let window = |a| {
+            let mut buffer = VecDeque::with_capacity(size);
+            buffer.push_back(a);
+            buffer
+        };
+
+        let initial = match parent.initial() {
+            SignalType::Constant(a) => SignalType::Constant(window(a)),
+            SignalType::Dynamic(a) => SignalType::Dynamic(window(a)),
+        };
+
+        WindowSignal {
+            config: config,
+            parent: parent,
+            size: size,
+            initial: initial,
+        }
+    }
+}
+
+impl<A> Signal<VecDeque<A>> for WindowSignal<A> where
+    A: 'static + Send + Clone,
+{
+    fn config(&self) -> Config {
+        self.config.clone()
+    }
+
+    fn initial(&self) -> SignalType<VecDeque<A>> {
+        self.initial.clone()
+    }
+
+    fn push_to(self: Box<Self>, target: Option<Box<Push<VecDeque<A>>>>) {
+        let inner = *self;
+        let WindowSignal { config: _, parent, size, initial } = inner;
+
+        debug!("SETUP: Window pushing to parent");
+        parent.push_to(
+            Some(
+                Box::new(
+                    WindowPusher {
+                        child: target,
+                        size: size,
+                        buffer: initial.unwrap(),
+                    }
+                )
+            )
+        );
+    }
+}
+impl<A> SignalExt<VecDeque<A>> for WindowSignal<A> where
+    A: 'static + Send + Clone,
+{}
+
+struct WindowPusher<A> {
+    child: Option<Box<Push<VecDeque<A>>>>,
+    size: usize,
+    buffer: VecDeque<A>,
+}
+
+impl<A> Push<A> for WindowPusher<A> where
+    A: 'static + Send + Clone,
+{
+    fn push(&mut self, event: Event<A>) {
+        let out = match event {
+            Event::Changed(a) => {
+                info!("RUN: WindowPusher handling Event::Changed");
+                if self.buffer.len() == self.size {
+                    self.buffer.pop_front();
+                }
+                self.buffer.push_back(a);
+
+                // Only copy the buffer if there's someone to send it to
+                if self.child.is_none() { return }
+                Event::Changed(self.buffer.clone())
+            },
+            Event::Unchanged => {
+                info!("RUN: WindowPusher handling Event::Unchanged");
+                Event::Unchanged
+            },
+            Event::Exit => {
+                info!("RUN: WindowPusher handling Event::Exit");
+                Event::Exit
+            },
+        };
+
+        match self.child {
+            Some(ref mut t) => t.push(out),
+            None => {},
+        }
+    }
+}
+
+/// The result of a `chunks` operation
+///
+pub struct ChunksSignal<A> where
+    A: 'static + Send + Clone,
+{
+    config: Config,
+    parent: Box<Signal<A>>,
+    size: usize,
+    initial: SignalType<Vec<A>>,
+}
+
+impl<A> ChunksSignal<A> where
+    A: 'static + Send + Clone,
+{
+    pub fn new(config: Config, parent: Box<Signal<A>>, size: usize) -> Self {
+        assert!(size > 0, "chunk size must be greater than zero");
+
+        let initial = match parent.initial() {
+            SignalType::Constant(_) => SignalType::Constant(Vec::new()),
+            SignalType::Dynamic(_) => SignalType::Dynamic(Vec::new()),
+        };
+
+        ChunksSignal {
+            config: config,
+            parent: parent,
+            size: size,
+            initial: initial,
+        }
+    }
+}
+
+impl<A> Signal<Vec<A>> for ChunksSignal<A> where
+    A: 'static + Send + Clone,
+{
+    fn config(&self) -> Config {
+        self.config.clone()
+    }
+
+    fn initial(&self) -> SignalType<Vec<A>> {
+        self.initial.clone()
+    }
+
+    fn push_to(self: Box<Self>, target: Option<Box<Push<Vec<A>>>>) {
+        let inner = *self;
+        let ChunksSignal { config: _, parent, size, initial: _ } = inner;
+
+        debug!("SETUP: Chunks pushing to parent");
+        parent.push_to(
+            Some(
+                Box::new(
+                    ChunksPusher {
+                        child: target,
+                        size: size,
+                        buffer: Vec::with_capacity(size),
+                    }
+                )
+            )
+        );
+    }
+}
+impl<A> SignalExt<Vec<A>> for ChunksSignal<A> where
+    A: 'static + Send + Clone,
+{}
+
+struct ChunksPusher<A> {
+    child: Option<Box<Push<Vec<A>>>>,
+    size: usize,
+    buffer: Vec<A>,
+}
+
+impl<A> Push<A> for ChunksPusher<A> where
+    A: 'static + Send + Clone,
+{
+    fn push(&mut self, event: Event<A>) {
+        let out = match event {
+            Event::Changed(a) => {
+                self.buffer.push(a);
+
+                if self.buffer.len() == self.size {
+                    info!("RUN: ChunksPusher handling Event::Changed, chunk full");
+                    let chunk = mem::replace(&mut self.buffer, Vec::with_capacity(self.size));
+                    Event::Changed(chunk)
+                } else {
+                    info!("RUN: ChunksPusher handling Event::Changed, buffering");
+                    Event::Unchanged
+                }
+            },
+            Event::Unchanged => {
+                info!("RUN: ChunksPusher handling Event::Unchanged");
+                Event::Unchanged
+            },
+            Event::Exit => {
+                info!("RUN: ChunksPusher handling Event::Exit");
+                Event::Exit
+            },
+        };
+
+        match self.child {
+            Some(ref mut t) => t.push(out),
+            None => {},
+        }
+    }
+}
@@ -1,8 +1,29 @@
-use super::{Signal, Builder, Value};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::hash::Hash;
+#[cfg(unix)]
+use std::io;
+#[cfg(unix)]
+use std::path::Path;
+
+use time;
+
+use super::{Signal, Builder, ThrottleMode, Value};
+#[cfg(unix)]
+use super::Framing;
 use primitives::lift::LiftSignal;
 use primitives::lift2::{Lift2Signal};
+use primitives::lift_all::LiftAllSignal;
+use primitives::liftn::{Lift3Signal, Lift4Signal, Lift5Signal, Lift6Signal, Lift7Signal, Lift8Signal};
 use primitives::fold::FoldSignal;
+use primitives::fold_n::{Fold2Signal, Fold3Signal};
+use primitives::drop_repeats::DropRepeatsSignal;
+use primitives::keep_if::KeepIfSignal;
+use primitives::filter_map::FilterMapSignal;
+use primitives::previous::PreviousSignal;
+use primitives::window::{WindowSignal, ChunksSignal};
 use primitives::fork::Branch;
+use primitives::sample_on::SampleOnSignal;
 
 /// Methods for manipulating signals
 /// 
@@ -86,6 +107,187 @@ pub trait SignalExt<A>: Signal<A> + Sized where
         Lift2Signal::new(self.config(), Box::new(self), Box::new(right), f)
     }
 
+    /// Combine three signals into an output signal
+    ///
+    /// Works like `lift2`; `f` is called once per tick with one `Value` for
+    /// each input, and only when at least one of the inputs changed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::default::Default;
+    /// use std::sync::mpsc::*;
+    /// use cfrp::*;
+    ///
+    /// let (a_tx, a_rx) = sync_channel(0);
+    /// let (c_tx, c_rx) = sync_channel(0);
+    /// let (out_tx, out_rx) = channel();
+    ///
+    /// spawn_topology(Default::default(), move |t| {
+    ///     t.listen(1 << 0, a_rx)
+    ///         .lift3(
+    ///             t.value(1 << 1),
+    ///             t.listen(1 << 2, c_rx),
+    ///             move |a, b, c| { out_tx.send(*a | *b | *c).unwrap() },
+    ///         )
+    ///         .add_to(t);
+    /// });
+    ///
+    /// // Initial value
+    /// assert_eq!(out_rx.recv().unwrap(), (1 << 0) | (1 << 1) | (1 << 2));
+    ///
+    /// a_tx.send(1 << 3).unwrap();
+    /// assert_eq!(out_rx.recv().unwrap(), (1 << 3) | (1 << 1) | (1 << 2));
+    ///
+    /// c_tx.send(1 << 4).unwrap();
+    /// assert_eq!(out_rx.recv().unwrap(), (1 << 3) | (1 << 1) | (1 << 4));
+    /// ```
+    ///
+    fn lift3<F, SB, SC, B, C, R>(mut self, mut b: SB, mut c: SC, f: F) -> Lift3Signal<F, A, B, C, R> where
+    SB: 'static + Signal<B>,
+    SC: 'static + Signal<C>,
+    F: 'static + Send + Fn(Value<A>, Value<B>, Value<C>) -> R,
+    B: 'static + Send + Clone,
+    C: 'static + Send + Clone,
+    R: 'static + Send + Clone,
+    {
+        self.init();
+        b.init();
+        c.init();
+
+        Lift3Signal::new(self.config(), Box::new(self), Box::new(b), Box::new(c), f)
+    }
+
+    /// Combine four signals into an output signal, see `lift3`
+    ///
+    fn lift4<F, SB, SC, SD, B, C, D, R>(mut self, mut b: SB, mut c: SC, mut d: SD, f: F) -> Lift4Signal<F, A, B, C, D, R> where
+    SB: 'static + Signal<B>,
+    SC: 'static + Signal<C>,
+    SD: 'static + Signal<D>,
+    F: 'static + Send + Fn(Value<A>, Value<B>, Value<C>, Value<D>) -> R,
+    B: 'static + Send + Clone,
+    C: 'static + Send + Clone,
+    D: 'static + Send + Clone,
+    R: 'static + Send + Clone,
+    {
+        self.init();
+        b.init();
+        c.init();
+        d.init();
+
+        Lift4Signal::new(self.config(), Box::new(self), Box::new(b), Box::new(c), Box::new(d), f)
+    }
+
+    /// Combine five signals into an output signal, see `lift3`
+    ///
+    fn lift5<F, SB, SC, SD, SE, B, C, D, E, R>(mut self, mut b: SB, mut c: SC, mut d: SD, mut e: SE, f: F) -> Lift5Signal<F, A, B, C, D, E, R> where
+    SB: 'static + Signal<B>,
+    SC: 'static + Signal<C>,
+    SD: 'static + Signal<D>,
+    SE: 'static + Signal<E>,
+    F: 'static + Send + Fn(Value<A>, Value<B>, Value<C>, Value<D>, Value<E>) -> R,
+    B: 'static + Send + Clone,
+    C: 'static + Send + Clone,
+    D: 'static + Send + Clone,
+    E: 'static + Send + Clone,
+    R: 'static + Send + Clone,
+    {
+        self.init();
+        b.init();
+        c.init();
+        d.init();
+        e.init();
+
+        Lift5Signal::new(self.config(), Box::new(self), Box::new(b), Box::new(c), Box::new(d), Box::new(e), f)
+    }
+
+    /// Combine six signals into an output signal, see `lift3`
+    ///
+    fn lift6<F, SB, SC, SD, SE, SG, B, C, D, E, G, R>(mut self, mut b: SB, mut c: SC, mut d: SD, mut e: SE, mut g: SG, f: F) -> Lift6Signal<F, A, B, C, D, E, G, R> where
+    SB: 'static + Signal<B>,
+    SC: 'static + Signal<C>,
+    SD: 'static + Signal<D>,
+    SE: 'static + Signal<E>,
+    SG: 'static + Signal<G>,
+    F: 'static + Send + Fn(Value<A>, Value<B>, Value<C>, Value<D>, Value<E>, Value<G>) -> R,
+    B: 'static + Send + Clone,
+    C: 'static + Send + Clone,
+    D: 'static + Send + Clone,
+    E: 'static + Send + Clone,
+    G: 'static + Send + Clone,
+    R: 'static + Send + Clone,
+    {
+        self.init();
+        b.init();
+        c.init();
+        d.init();
+        e.init();
+        g.init();
+
+        Lift6Signal::new(self.config(), Box::new(self), Box::new(b), Box::new(c), Box::new(d), Box::new(e), Box::new(g), f)
+    }
+
+    /// Combine seven signals into an output signal, see `lift3`
+    ///
+    fn lift7<F, SB, SC, SD, SE, SG, SH, B, C, D, E, G, H, R>(mut self, mut b: SB, mut c: SC, mut d: SD, mut e: SE, mut g: SG, mut h: SH, f: F) -> Lift7Signal<F, A, B, C, D, E, G, H, R> where
+    SB: 'static + Signal<B>,
+    SC: 'static + Signal<C>,
+    SD: 'static + Signal<D>,
+    SE: 'static + Signal<E>,
+    SG: 'static + Signal<G>,
+    SH: 'static + Signal<H>,
+    F: 'static + Send + Fn(Value<A>, Value<B>, Value<C>, Value<D>, Value<E>, Value<G>, Value<H>) -> R,
+    B: 'static + Send + Clone,
+    C: 'static + Send + Clone,
+    D: 'static + Send + Clone,
+    E: 'static + Send + Clone,
+    G: 'static + Send + Clone,
+    H: 'static + Send + Clone,
+    R: 'static + Send + Clone,
+    {
+        self.init();
+        b.init();
+        c.init();
+        d.init();
+        e.init();
+        g.init();
+        h.init();
+
+        Lift7Signal::new(self.config(), Box::new(self), Box::new(b), Box::new(c), Box::new(d), Box::new(e), Box::new(g), Box::new(h), f)
+    }
+
+    /// Combine eight signals into an output signal, see `lift3`
+    ///
+    fn lift8<F, SB, SC, SD, SE, SG, SH, SI, B, C, D, E, G, H, I, R>(mut self, mut b: SB, mut c: SC, mut d: SD, mut e: SE, mut g: SG, mut h: SH, mut i: SI, f: F) -> Lift8Signal<F, A, B, C, D, E, G, H, I, R> where
+    SB: 'static + Signal<B>,
+    SC: 'static + Signal<C>,
+    SD: 'static + Signal<D>,
+    SE: 'static + Signal<E>,
+    SG: 'static + Signal<G>,
+    SH: 'static + Signal<H>,
+    SI: 'static + Signal<I>,
+    F: 'static + Send + Fn(Value<A>, Value<B>, Value<C>, Value<D>, Value<E>, Value<G>, Value<H>, Value<I>) -> R,
+    B: 'static + Send + Clone,
+    C: 'static + Send + Clone,
+    D: 'static + Send + Clone,
+    E: 'static + Send + Clone,
+    G: 'static + Send + Clone,
+    H: 'static + Send + Clone,
+    I: 'static + Send + Clone,
+    R: 'static + Send + Clone,
+    {
+        self.init();
+        b.init();
+        c.init();
+        d.init();
+        e.init();
+        g.init();
+        h.init();
+        i.init();
+
+        Lift8Signal::new(self.config(), Box::new(self), Box::new(b), Box::new(c), Box::new(d), Box::new(e), Box::new(g), Box::new(h), Box::new(i), f)
+    }
+
     /// Merge data from a signal into an accumulator and return a signal with
     /// the accumulator's value
     ///
@@ -122,18 +324,272 @@ pub trait SignalExt<A>: Signal<A> + Sized where
         FoldSignal::new(self.config(), Box::new(self), initial, f)
     }
 
+    /// Merge data from two signals into an accumulator and return a signal
+    /// with the accumulator's value
+    ///
+    /// Like `lift2`, each input is wrapped in a `Value` and `f` is only
+    /// called when at least one of the inputs changed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::default::Default;
+    /// use std::sync::mpsc::*;
+    /// use cfrp::*;
+    ///
+    /// let (cmd_tx, cmd_rx) = sync_channel(0);
+    /// let (tick_tx, tick_rx) = sync_channel(0);
+    /// let (out_tx, out_rx) = channel();
+    ///
+    /// spawn_topology(Default::default(), move |t| {
+    ///     t.listen(0, cmd_rx)
+    ///         .fold2(t.listen((), tick_rx), 0, |sum, cmd, tick| {
+    ///             match (cmd, tick) {
+    ///                 (Value::Changed(i), _) => sum + i,
+    ///                 (_, Value::Changed(_)) => sum * 2,
+    ///                 _ => sum,
+    ///             }
+    ///         })
+    ///         .lift(move |i| { out_tx.send(i).unwrap(); })
+    ///         .add_to(t);
+    /// });
+    ///
+    /// // Initial value
+    /// assert_eq!(out_rx.recv().unwrap(), 0);
+    ///
+    /// cmd_tx.send(1).unwrap();
+    /// assert_eq!(out_rx.recv().unwrap(), 1);
+    ///
+    /// tick_tx.send(()).unwrap();
+    /// assert_eq!(out_rx.recv().unwrap(), 2);
+    /// ```
+    ///
+    fn fold2<F, SB, B, S>(mut self, mut right: SB, initial: S, f: F) -> Fold2Signal<F, A, B, S> where
+    SB: 'static + Signal<B>,
+    F: 'static + Send + Fn(S, Value<A>, Value<B>) -> S,
+    B: 'static + Send + Clone,
+    S: 'static + Send + Clone,
+    {
+        self.init();
+        right.init();
+
+        Fold2Signal::new(self.config(), Box::new(self), Box::new(right), initial, f)
+    }
+
+    /// Merge data from three signals into an accumulator, see `fold2`
+    ///
+    fn fold3<F, SB, SC, B, C, S>(mut self, mut b: SB, mut c: SC, initial: S, f: F) -> Fold3Signal<F, A, B, C, S> where
+    SB: 'static + Signal<B>,
+    SC: 'static + Signal<C>,
+    F: 'static + Send + Fn(S, Value<A>, Value<B>, Value<C>) -> S,
+    B: 'static + Send + Clone,
+    C: 'static + Send + Clone,
+    S: 'static + Send + Clone,
+    {
+        self.init();
+        b.init();
+        c.init();
+
+        Fold3Signal::new(self.config(), Box::new(self), Box::new(b), Box::new(c), initial, f)
+    }
+
+    /// Forward the signal in `branches` selected by the value of this signal
+    ///
+    /// Events from every branch are consumed, but only changes to the
+    /// currently selected branch are forwarded.  When the selection changes
+    /// the newly selected branch's most recent value is emitted.  If the
+    /// selector's value isn't a key of `branches` the previous selection is
+    /// kept.
+    ///
+    /// Panics if `branches` is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::default::Default;
+    /// use std::collections::HashMap;
+    /// use std::sync::mpsc::*;
+    /// use cfrp::*;
+    ///
+    /// let (mode_tx, mode_rx) = sync_channel(0);
+    /// let (a_tx, a_rx) = sync_channel(0);
+    /// let (b_tx, b_rx) = sync_channel(0);
+    /// let (out_tx, out_rx) = channel();
+    ///
+    /// spawn_topology(Default::default(), move |t| {
+    ///     let mut branches = HashMap::new();
+    ///     branches.insert("a", t.listen(0, a_rx));
+    ///     branches.insert("b", t.listen(10, b_rx));
+    ///
+    ///     t.listen("a", mode_rx)
+    ///         .switch(branches)
+    ///         .lift(move |i| { out_tx.send(i).unwrap(); })
+    ///         .add_to(t);
+    /// });
+    ///
+    /// // Initial value
+    /// assert_eq!(out_rx.recv().unwrap(), 0);
+    ///
+    /// // Changes to the unselected branch aren't forwarded
+    /// b_tx.send(11).unwrap();
+    /// a_tx.send(1).unwrap();
+    /// assert_eq!(out_rx.recv().unwrap(), 1);
+    ///
+    /// // Switching emits the selected branch's latest value
+    /// mode_tx.send("b").unwrap();
+    /// assert_eq!(out_rx.recv().unwrap(), 11);
+    /// ```
+    ///
+    fn switch<SB, B>(self, branches: HashMap<A, SB>) -> Box<Signal<B>> where
+    SB: 'static + Signal<B>,
+    B: 'static + Send + Clone,
+    A: Eq + Hash,
+    {
+        let mut indices = HashMap::new();
+        let mut signals = Vec::new();
+
+        for (k, branch) in branches.into_iter() {
+            indices.insert(k, signals.len());
+            signals.push(branch);
+        }
+
+        self.switch_by(signals, move |k: &A| -> Option<usize> { indices.get(k).cloned() })
+    }
+
+    /// Like `switch`, but the selected branch is the index into `branches`
+    /// returned by `f`
+    ///
+    /// If `f` returns `None` or an index outside `branches` the previous
+    /// selection is kept.
+    ///
+    fn switch_by<F, SB, B>(mut self, branches: Vec<SB>, f: F) -> Box<Signal<B>> where
+    F: 'static + Send + Fn(&A) -> Option<usize>,
+    SB: 'static + Signal<B>,
+    B: 'static + Send + Clone,
+    {
+        assert!(!branches.is_empty(), "switch requires at least one branch");
+
+        self.init();
+
+        let config = self.config();
+        let branches = branches.into_iter().map(|mut b| {
+            b.init();
+            Box::new(b) as Box<Signal<B>>
+        }).collect::<Vec<Box<Signal<B>>>>();
+        let default = branches[0].initial().unwrap();
+
+        // Reads every branch each tick so no branch is ever blocked
+        let combined = LiftAllSignal::new(config, branches, |bs: Vec<Value<B>>| -> Vec<Value<B>> { bs });
+
+        let selected = RefCell::new(0);
+        let switched = self.lift2(combined, move |k: Value<A>, bs: Value<Vec<Value<B>>>| -> Option<B> {
+            // An unchanged `bs` is the previous tick's cache, so its entries
+            // may still be marked as changed
+            let (bs_changed, mut bs) = match bs {
+                Value::Changed(bs) => (true, bs),
+                Value::Unchanged(bs) => (false, bs),
+            };
+
+            if let Value::Changed(ref k) = k {
+                match f(k) {
+                    Some(i) if i < bs.len() => {
+                        *selected.borrow_mut() = i;
+                        return Some(bs.swap_remove(i).into_inner())
+                    },
+                    _ => {},
+                }
+            }
+
+            if !bs_changed { return None }
+
+            match bs.swap_remove(*selected.borrow()) {
+                Value::Changed(b) => Some(b),
+                Value::Unchanged(_) => None,
+            }
+        });
+
+        Box::new(switched.filter_map(|b: Option<B>| -> Option<B> { b }, default))
+    }
+
     /// Sugar for `Builder::add`
     ///
     fn add_to(self, builder: &Builder) -> Branch<A> {
         builder.add(self)
     }
 
+    /// Sugar for `Builder::partition`
+    ///
+    fn partition<F>(self, builder: &Builder, f: F) -> (Branch<A>, Branch<A>) where
+    F: 'static + Send + Fn(&A) -> bool,
+    {
+        builder.partition(self, f)
+    }
+
+    /// Sugar for `Builder::try_lift`
+    ///
+    fn try_lift<F, B, E>(self, builder: &Builder, f: F, default: B, default_err: E) -> (Branch<B>, Branch<E>) where
+    F: 'static + Send + Fn(A) -> Result<B, E>,
+    B: 'static + Send + Clone,
+    E: 'static + Send + Clone,
+    {
+        builder.try_lift(self, f, default, default_err)
+    }
+
+    /// Sugar for `Builder::unzip`
+    ///
+    fn unzip<B, C>(self, builder: &Builder) -> (Branch<B>, Branch<C>) where
+    Self: Signal<(B, C)>,
+    B: 'static + Send + Clone + PartialEq,
+    C: 'static + Send + Clone + PartialEq,
+    {
+        builder.unzip(self)
+    }
+
+    /// Sugar for `Builder::unzip_all`
+    ///
+    fn unzip_all<B, C>(self, builder: &Builder) -> (Branch<B>, Branch<C>) where
+    Self: Signal<(B, C)>,
+    B: 'static + Send + Clone,
+    C: 'static + Send + Clone,
+    {
+        builder.unzip_all(self)
+    }
+
     /// Sugar for `Builder::async`
     ///
     fn async(self, builder: &Builder) -> Branch<A> {
         builder.async(self)
     }
 
+    /// Sugar for `Builder::debounce`
+    ///
+    fn debounce(self, builder: &Builder, duration: time::Duration) -> Branch<A> {
+        builder.debounce(self, duration)
+    }
+
+    /// Sugar for `Builder::throttle`
+    ///
+    fn throttle(self, builder: &Builder, duration: time::Duration, mode: ThrottleMode) -> Branch<A> {
+        builder.throttle(self, duration, mode)
+    }
+
+    /// Sugar for `Builder::delay`
+    ///
+    fn delay(self, builder: &Builder, duration: time::Duration) -> Branch<A> {
+        builder.delay(self, duration)
+    }
+
+    /// Sugar for `Builder::write_unix`
+    ///
+    #[cfg(unix)]
+    fn write_unix<P, Fr, F>(self, builder: &Builder, path: P, framing: Fr, encode: F) -> io::Result<()> where
+    P: AsRef<Path>,
+    Fr: Framing,
+    F: 'static + Send + Fn(&A) -> Vec<u8>,
+    {
+        builder.write_unix(self, path, framing, encode)
+    }
+
     /// Alias of `lift`
     fn map<F, B>(self, f: F) -> LiftSignal<F, A, B> where
     F: 'static + Send + Fn(A) -> B,
@@ -190,6 +646,163 @@ pub trait SignalExt<A>: Signal<A> + Sized where
         )
     }
 
+    /// Merge two signals of the same type into a signal containing whichever
+    /// value changed most recently
+    ///
+    /// Equivalent to Elm's `merge`.  If both signals change during the same
+    /// tick the left value wins.  The initial value is the initial value of
+    /// the left signal.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::default::Default;
+    /// use std::sync::mpsc::*;
+    /// use cfrp::*;
+    ///
+    /// let (l_tx, l_rx) = sync_channel(0);
+    /// let (r_tx, r_rx) = sync_channel(0);
+    /// let (out_tx, out_rx) = channel();
+    ///
+    /// spawn_topology(Default::default(), move |t| {
+    ///     t.listen(0, l_rx)
+    ///         .merge(t.listen(1, r_rx))
+    ///         .lift(move |i| { out_tx.send(i).unwrap(); })
+    ///         .add_to(t);
+    /// });
+    ///
+    /// // Initial value
+    /// assert_eq!(out_rx.recv().unwrap(), 0);
+    ///
+    /// r_tx.send(2).unwrap();
+    /// assert_eq!(out_rx.recv().unwrap(), 2);
+    ///
+    /// l_tx.send(3).unwrap();
+    /// assert_eq!(out_rx.recv().unwrap(), 3);
+    /// ```
+    ///
+    fn merge<SB>(self, right: SB) -> Box<Signal<A>> where
+    SB: 'static + Signal<A>,
+    {
+        Box::new(
+            self.lift2(
+                right,
+                |l: Value<A>, r: Value<A>| -> A {
+                    match (l, r) {
+                        (Value::Changed(l), _) => l,
+                        (Value::Unchanged(_), Value::Changed(r)) => r,
+                        (Value::Unchanged(l), Value::Unchanged(_)) => l,
+                    }
+                }
+            )
+        )
+    }
+
+    /// Sample the current value of a signal each time `trigger` changes
+    ///
+    /// Equivalent to Elm's `sampleOn`.  Changes to the sampled signal alone
+    /// result in no change to the output, so nodes downstream of the output
+    /// are only recomputed when `trigger` changes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::default::Default;
+    /// use std::sync::mpsc::*;
+    /// use cfrp::*;
+    ///
+    /// let (data_tx, data_rx) = sync_channel(0);
+    /// let (trigger_tx, trigger_rx) = sync_channel(0);
+    /// let (seen_tx, seen_rx) = channel();
+    /// let (out_tx, out_rx) = channel();
+    ///
+    /// spawn_topology(Default::default(), move |t| {
+    ///     let data = t.listen(0, data_rx);
+    ///
+    ///     data.clone()
+    ///         .lift(move |i| { seen_tx.send(i).unwrap(); })
+    ///         .add_to(t);
+    ///
+    ///     data.sample_on(t.listen((), trigger_rx))
+    ///         .lift(move |i| { out_tx.send(i).unwrap(); })
+    ///         .add_to(t);
+    /// });
+    ///
+    /// // Initial value
+    /// assert_eq!(seen_rx.recv().unwrap(), 0);
+    /// assert_eq!(out_rx.recv().unwrap(), 0);
+    ///
+    /// // Not sampled until the trigger fires
+    /// data_tx.send(1).unwrap();
+    /// assert_eq!(seen_rx.recv().unwrap(), 1);
+    /// assert!(out_rx.try_recv().is_err());
+    ///
+    /// trigger_tx.send(()).unwrap();
+    /// assert_eq!(out_rx.recv().unwrap(), 1);
+    /// ```
+    ///
+    fn sample_on<ST, T>(self, trigger: ST) -> SampleOnSignal<T, A> where
+    ST: 'static + Signal<T>,
+    T: 'static + Send + Clone,
+    {
+        let config = self.config();
+        let trigger_type = trigger.initial();
+
+        SampleOnSignal::new(config, self.zip(trigger), trigger_type)
+    }
+
+    /// Suppress changes which are equal to the previous value
+    ///
+    /// Equivalent to Elm's `dropRepeats`.  Repeated values are reported as
+    /// unchanged, so nodes downstream of the output aren't recomputed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::default::Default;
+    /// use std::sync::mpsc::*;
+    /// use cfrp::*;
+    ///
+    /// let (in_tx, in_rx) = sync_channel(0);
+    /// let (out_tx, out_rx) = channel();
+    ///
+    /// spawn_topology(Default::default(), move |t| {
+    ///     t.listen(0, in_rx)
+    ///         .drop_repeats()
+    ///         .lift(move |i| { out_tx.send(i).unwrap(); })
+    ///         .add_to(t);
+    /// });
+    ///
+    /// // Initial value
+    /// assert_eq!(out_rx.recv().unwrap(), 0);
+    ///
+    /// in_tx.send(1).unwrap();
+    /// in_tx.send(1).unwrap();
+    /// in_tx.send(2).unwrap();
+    /// assert_eq!(out_rx.recv().unwrap(), 1);
+    /// assert_eq!(out_rx.recv().unwrap(), 2);
+    /// ```
+    ///
+    fn drop_repeats(self) -> Box<Signal<A>> where
+    A: PartialEq,
+    {
+        Box::new(self.drop_repeats_by(|a: &A| -> A { a.clone() }))
+    }
+
+    /// Suppress changes which map to the same key as the previous value
+    ///
+    /// Like `drop_repeats`, but compares the result of `f` rather than the
+    /// values themselves.
+    ///
+    fn drop_repeats_by<F, K>(mut self, f: F) -> DropRepeatsSignal<F, A, K> where
+    F: 'static + Send + Fn(&A) -> K,
+    K: 'static + Send + PartialEq,
+    {
+        self.init();
+
+        DropRepeatsSignal::new(self.config(), Box::new(self), f)
+    }
+
     /// Same as `Iterator::enumerate`.  
     ///
     /// The counter only increments when upstream data changes.  To track 
@@ -231,6 +844,168 @@ pub trait SignalExt<A>: Signal<A> + Sized where
         )
     }
 
+    /// Returns a signal containing the previous and current value of the
+    /// input signal
+    ///
+    /// The initial value contains the input signal's initial value twice.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::default::Default;
+    /// use std::sync::mpsc::*;
+    /// use cfrp::*;
+    ///
+    /// let (in_tx, in_rx) = sync_channel(0);
+    /// let (out_tx, out_rx) = channel();
+    ///
+    /// spawn_topology(Default::default(), move |t| {
+    ///     t.listen(0, in_rx)
+    ///         .pairwise()
+    ///         .lift(move |i| { out_tx.send(i).unwrap(); })
+    ///         .add_to(t);
+    /// });
+    ///
+    /// // Initial value
+    /// assert_eq!(out_rx.recv().unwrap(), (0, 0));
+    ///
+    /// in_tx.send(1).unwrap();
+    /// assert_eq!(out_rx.recv().unwrap(), (0, 1));
+    ///
+    /// in_tx.send(3).unwrap();
+    /// assert_eq!(out_rx.recv().unwrap(), (1, 3));
+    /// ```
+    ///
+    fn pairwise(self) -> Box<Signal<(A, A)>>
+    {
+        let initial = self.initial().unwrap();
+        Box::new(
+            self.fold(
+                (initial.clone(), initial),
+                |state: (A, A), i: A| { (state.1, i) },
+            )
+        )
+    }
+
+    /// Returns a signal which lags the input signal by one change
+    ///
+    /// The output starts with `default` and takes the input's previous value
+    /// each time the input changes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::default::Default;
+    /// use std::sync::mpsc::*;
+    /// use cfrp::*;
+    ///
+    /// let (in_tx, in_rx) = sync_channel(0);
+    /// let (out_tx, out_rx) = channel();
+    ///
+    /// spawn_topology(Default::default(), move |t| {
+    ///     t.listen(1, in_rx)
+    ///         .previous(0)
+    ///         .lift(move |i| { out_tx.send(i).unwrap(); })
+    ///         .add_to(t);
+    /// });
+    ///
+    /// // Initial value
+    /// assert_eq!(out_rx.recv().unwrap(), 0);
+    ///
+    /// in_tx.send(2).unwrap();
+    /// assert_eq!(out_rx.recv().unwrap(), 1);
+    ///
+    /// in_tx.send(3).unwrap();
+    /// assert_eq!(out_rx.recv().unwrap(), 2);
+    /// ```
+    ///
+    fn previous(mut self, default: A) -> PreviousSignal<A> {
+        self.init();
+
+        PreviousSignal::new(self.config(), Box::new(self), default)
+    }
+
+    /// Returns a signal containing the last `n` values of the input signal
+    ///
+    /// The window starts with the input's initial value and is only updated
+    /// when the input changes.  Panics if `n` is zero.
+    ///
+    /// The window is kept in place as values arrive, and is only copied
+    /// when it's sent downstream.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::default::Default;
+    /// use std::sync::mpsc::*;
+    /// use cfrp::*;
+    ///
+    /// let (in_tx, in_rx) = sync_channel(0);
+    /// let (out_tx, out_rx) = channel();
+    ///
+    /// spawn_topology(Default::default(), move |t| {
+    ///     t.listen(0, in_rx)
+    ///         .window(2)
+    ///         .lift(move |w| { out_tx.send(w.into_iter().collect::<Vec<_>>()).unwrap(); })
+    ///         .add_to(t);
+    /// });
+    ///
+    /// // Initial value
+    /// assert_eq!(out_rx.recv().unwrap(), vec![0]);
+    ///
+    /// in_tx.send(1).unwrap();
+    /// assert_eq!(out_rx.recv().unwrap(), vec![0, 1]);
+    ///
+    /// in_tx.send(2).unwrap();
+    /// assert_eq!(out_rx.recv().unwrap(), vec![1, 2]);
+    /// ```
+    ///
+    fn window(mut self, n: usize) -> WindowSignal<A> {
+        self.init();
+
+        WindowSignal::new(self.config(), Box::new(self), n)
+    }
+
+    /// Returns a signal containing non-overlapping groups of `n` values from
+    /// the input signal
+    ///
+    /// The output only changes once `n` changes to the input have been
+    /// collected.  The initial value is empty.  Panics if `n` is zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::default::Default;
+    /// use std::sync::mpsc::*;
+    /// use cfrp::*;
+    ///
+    /// let (in_tx, in_rx) = sync_channel(0);
+    /// let (out_tx, out_rx) = channel();
+    ///
+    /// spawn_topology(Default::default(), move |t| {
+    ///     t.listen(0, in_rx)
+    ///         .chunks(2)
+    ///         .lift(move |c| { out_tx.send(c).unwrap(); })
+    ///         .add_to(t);
+    /// });
+    ///
+    /// // Initial value
+    /// assert_eq!(out_rx.recv().unwrap(), vec![]);
+    ///
+    /// in_tx.send(1).unwrap();
+    /// in_tx.send(2).unwrap();
+    /// in_tx.send(3).unwrap();
+    /// in_tx.send(4).unwrap();
+    /// assert_eq!(out_rx.recv().unwrap(), vec![1, 2]);
+    /// assert_eq!(out_rx.recv().unwrap(), vec![3, 4]);
+    /// ```
+    ///
+    fn chunks(mut self, n: usize) -> ChunksSignal<A> {
+        self.init();
+
+        ChunksSignal::new(self.config(), Box::new(self), n)
+    }
+
     /// Filter an input stream by a predicate function `F`.  
     ///
     /// In this case 'filtered' is reflected by a value of `None`
@@ -276,6 +1051,96 @@ pub trait SignalExt<A>: Signal<A> + Sized where
         )
     }
 
+    /// Keep only the values which satisfy the predicate `f`
+    ///
+    /// Equivalent to Elm's `keepIf`.  Unlike `filter`, rejected values are
+    /// reported as unchanged rather than `None`, so the output keeps the
+    /// last accepted value.  If the initial value doesn't satisfy `f`,
+    /// `default` is used instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::default::Default;
+    /// use std::sync::mpsc::*;
+    /// use cfrp::*;
+    ///
+    /// let (in_tx, in_rx) = sync_channel(0);
+    /// let (out_tx, out_rx) = channel();
+    ///
+    /// spawn_topology(Default::default(), move |t| {
+    ///     t.listen(1, in_rx)
+    ///         .keep_if(|i| { i % 2 == 0 }, 0)
+    ///         .lift(move |i| { out_tx.send(i).unwrap(); })
+    ///         .add_to(t);
+    /// });
+    ///
+    /// // Initial value fails the predicate
+    /// assert_eq!(out_rx.recv().unwrap(), 0);
+    ///
+    /// in_tx.send(3).unwrap();
+    /// in_tx.send(4).unwrap();
+    /// assert_eq!(out_rx.recv().unwrap(), 4);
+    /// ```
+    ///
+    fn keep_if<F>(mut self, f: F, default: A) -> KeepIfSignal<F, A> where
+    F: 'static + Send + Fn(&A) -> bool,
+    {
+        self.init();
+
+        KeepIfSignal::new(self.config(), Box::new(self), f, default)
+    }
+
+    /// Drop the values which satisfy the predicate `f`
+    ///
+    /// Equivalent to Elm's `dropIf`, the inverse of `keep_if`.
+    ///
+    fn drop_if<F>(self, f: F, default: A) -> Box<Signal<A>> where
+    F: 'static + Send + Fn(&A) -> bool,
+    {
+        Box::new(self.keep_if(move |a: &A| -> bool { !f(a) }, default))
+    }
+
+    /// Transform and filter a signal in a single step
+    ///
+    /// Values for which `f` returns `Some(b)` are output as `b`, values for
+    /// which `f` returns `None` are reported as unchanged.  If `f` returns
+    /// `None` for the initial value, `default` is used instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::default::Default;
+    /// use std::sync::mpsc::*;
+    /// use cfrp::*;
+    ///
+    /// let (in_tx, in_rx) = sync_channel(0);
+    /// let (out_tx, out_rx) = channel();
+    ///
+    /// spawn_topology(Default::default(), move |t| {
+    ///     t.listen("1", in_rx)
+    ///         .filter_map(|s| { s.parse::<usize>().ok() }, 0)
+    ///         .lift(move |i| { out_tx.send(i).unwrap(); })
+    ///         .add_to(t);
+    /// });
+    ///
+    /// // Initial value
+    /// assert_eq!(out_rx.recv().unwrap(), 1);
+    ///
+    /// in_tx.send("foo").unwrap();
+    /// in_tx.send("2").unwrap();
+    /// assert_eq!(out_rx.recv().unwrap(), 2);
+    /// ```
+    ///
+    fn filter_map<F, B>(mut self, f: F, default: B) -> FilterMapSignal<F, A, B> where
+    F: 'static + Send + Fn(A) -> Option<B>,
+    B: 'static + Send + Clone,
+    {
+        self.init();
+
+        FilterMapSignal::new(self.config(), Box::new(self), f, default)
+    }
+
     /// Pass each value in a signal to `F` before sending it to an output 
     /// signal.  
     ///
@@ -1,7 +1,9 @@
+use std::any::Any;
+use std::collections::HashMap;
 use std::sync::*;
 use std::thread;
 
-use super::Run;
+use super::{Run, Address};
 use primitives::input::{RunInput, NoOp};
 
 
@@ -10,20 +12,32 @@ use primitives::input::{RunInput, NoOp};
 pub struct Topology {
     inputs: Vec<Box<RunInput>>,
     runners: Vec<Box<Run>>,
+    addresses: HashMap<String, Box<Any + Send>>,
 }
 
 impl Topology {
     /// Create a new topology
     ///
     pub fn new(inputs: Vec<Box<RunInput>>, runners: Vec<Box<Run>>) -> Self {
-        Topology { inputs: inputs, runners: runners }
+        Topology {
+            inputs: inputs,
+            runners: runners,
+            addresses: HashMap::new(),
+        }
+    }
+
+    /// Make named addresses available through the topology's handle
+    ///
+    pub fn with_addresses(mut self, addresses: HashMap<String, Box<Any + Send>>) -> Self {
+        self.addresses = addresses;
+        self
     }
 
     /// Run the topology
     ///
     pub fn run(self) -> TopologyHandle {
         info!("----> TOPOLOGY STARTING");
-        let Topology {inputs, runners} = self;
+        let Topology {inputs, runners, addresses} = self;
 
         for runner in runners.into_iter() {
             thread::spawn(move || {
@@ -44,6 +58,7 @@ impl Topology {
 
         TopologyHandle {
             term_txs: term_txs,
+            addresses: addresses,
         }
     }
 }
@@ -52,6 +67,7 @@ impl Topology {
 ///
 pub struct TopologyHandle {
     term_txs: Vec<Box<NoOp>>,
+    addresses: HashMap<String, Box<Any + Send>>,
 }
 
 // NOTE: Drop?  seems to kill tests for some reason, maybe because not capturing
@@ -62,4 +78,15 @@ impl TopologyHandle {
         }
         debug!("----> TOPOLOGY DROPPED");
     }
+
+    /// Returns the address created by `Builder::named_mailbox` with `name`
+    ///
+    /// Returns `None` if there is no such address, or if its type isn't
+    /// `Address<A>`.
+    ///
+    pub fn address<A>(&self, name: &str) -> Option<Address<A>> where
+        A: 'static + Send,
+    {
+        self.addresses.get(name).and_then(|a| a.downcast_ref::<Address<A>>()).cloned()
+    }
 }